@@ -0,0 +1,142 @@
+use crate::money::Currency;
+use crate::roulette::{total_wager, PlaceBetError, RouletteBet, RouletteEvaluator, ZeroRule};
+use crate::wheel::WheelVariant;
+
+/// Exact statistics of a set of bets over a single spin, under the standard zero rule. They are computed by settling
+/// the bets against every pocket of the wheel, so no simulation is involved. Net amounts are from the player's side.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BetAnalysis {
+    pub total_wager: u64,
+
+    /// Expected gross payout, stakes included
+    pub expected_return: f64,
+
+    /// Expected net result, i.e. the expected return minus the total wager
+    pub expected_net: f64,
+
+    /// Variance of the net result
+    pub variance: f64,
+
+    pub std_dev: f64,
+
+    /// Probability that the payout is larger than the total wager
+    pub win_probability: f64,
+
+    /// Expected loss as a fraction of the total wager (0 when nothing is wagered)
+    pub house_edge: f64,
+}
+
+impl BetAnalysis {
+    /// Fails with ```PayoutOverflow``` if a payout, or the total wager, doesn't fit in a ```u64```, and with
+    /// ```CurrencyMismatch``` if the bets are not all in the currency of the first one
+    pub fn new(variant: WheelVariant, bets: &[RouletteBet]) -> Result<Self, PlaceBetError> {
+        let currency = bets.first().map_or(Currency::Chips, |bet| bet.currency());
+        let total_wager = total_wager(bets, currency)?.minor_units();
+        let pockets = variant.pocket_count() as i128;
+
+        // Sums over all pockets, kept as integers so the moments are exact until the final division
+        let mut payout_sum = 0i128;
+        let mut net_sum = 0i128;
+        let mut net_square_sum = 0i128;
+        let mut winning_pockets = 0i128;
+
+        for pocket in variant.pockets() {
+            let payout: i128 = RouletteEvaluator::calculate_winnings(variant, ZeroRule::Standard, pocket, bets)?
+                .iter()
+                .map(|result| result.win() as i128)
+                .sum();
+            let net = payout - total_wager as i128;
+
+            payout_sum += payout;
+            net_sum += net;
+            net_square_sum += net * net;
+            if net > 0 {
+                winning_pockets += 1;
+            }
+        }
+
+        let variance = (pockets * net_square_sum - net_sum * net_sum) as f64 / (pockets * pockets) as f64;
+        let expected_net = net_sum as f64 / pockets as f64;
+
+        Ok(Self {
+            total_wager,
+            expected_return: payout_sum as f64 / pockets as f64,
+            expected_net,
+            variance,
+            std_dev: variance.sqrt(),
+            win_probability: winning_pockets as f64 / pockets as f64,
+            house_edge: if total_wager > 0 { -expected_net / total_wager as f64 } else { 0.0 },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::outside::RedBlack;
+    use crate::roulette::RouletteBetType;
+    use crate::wheel::DOUBLE_ZERO;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn straight_up() {
+        let bets = vec![RouletteBet::new(RouletteBetType::Straight(17), 1)];
+        let analysis = BetAnalysis::new(WheelVariant::European, &bets).unwrap();
+
+        assert_eq!(analysis.total_wager, 1);
+        assert_close(analysis.expected_return, 36.0 / 37.0);
+        assert_close(analysis.expected_net, -1.0 / 37.0);
+        assert_close(analysis.house_edge, 1.0 / 37.0);
+        assert_close(analysis.variance, 46656.0 / 1369.0);
+        assert_close(analysis.std_dev, (46656.0f64 / 1369.0).sqrt());
+        assert_close(analysis.win_probability, 1.0 / 37.0);
+
+        let analysis = BetAnalysis::new(WheelVariant::American, &bets).unwrap();
+        assert_close(analysis.house_edge, 2.0 / 38.0);
+    }
+
+    #[test]
+    fn bet_sets() {
+        // five number bet is the worst bet on an American table
+        let bets = vec![RouletteBet::new(RouletteBetType::Fiveline([0, 1, 2, 3, DOUBLE_ZERO]), 10)];
+        assert_close(BetAnalysis::new(WheelVariant::American, &bets).unwrap().house_edge, 3.0 / 38.0);
+
+        // red and black together only lose on zero
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 10),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Black), 10),
+        ];
+        let analysis = BetAnalysis::new(WheelVariant::European, &bets).unwrap();
+        assert_close(analysis.expected_net, -20.0 / 37.0);
+        assert_close(analysis.win_probability, 0.0);
+        assert_close(analysis.house_edge, 1.0 / 37.0);
+    }
+
+    #[test]
+    fn overflow() {
+        let huge = RouletteBet::new(RouletteBetType::Straight(17), u64::MAX / 10);
+        assert_eq!(BetAnalysis::new(WheelVariant::European, &[huge]), Err(PlaceBetError::PayoutOverflow(huge)));
+
+        let half = RouletteBet::new(RouletteBetType::Straight(0), u64::MAX / 2 + 1);
+        assert_eq!(BetAnalysis::new(WheelVariant::European, &[half, half]), Err(PlaceBetError::PayoutOverflow(half)));
+    }
+
+    #[test]
+    fn mixed_currencies() {
+        let chips = RouletteBet::new(RouletteBetType::Straight(17), 10);
+        let euros = chips.in_currency(Currency::Eur);
+        assert_eq!(BetAnalysis::new(WheelVariant::European, &[chips, euros]), Err(PlaceBetError::CurrencyMismatch(euros, Currency::Chips)));
+        assert_eq!(BetAnalysis::new(WheelVariant::European, &[euros, euros]).unwrap().total_wager, 20);
+    }
+
+    #[test]
+    fn no_bets() {
+        let analysis = BetAnalysis::new(WheelVariant::European, &[]).unwrap();
+        assert_eq!(analysis.total_wager, 0);
+        assert_close(analysis.variance, 0.0);
+        assert_close(analysis.house_edge, 0.0);
+    }
+}
@@ -0,0 +1,299 @@
+use std::fmt;
+use crate::roulette::{Roulette, RouletteBet, RouletteBetType, PlaceBetError};
+use crate::wheel::{WheelVariant, Pocket};
+
+/// Announced (racetrack) bets. Each announcement covers a section of the wheel and expands into a fixed set of
+/// inside bets, each carrying a multiple of the chip value. The classic sections only exist on the European wheel.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(from = "crate::schema::AnnouncedBetRepr", into = "crate::schema::AnnouncedBetRepr"))]
+pub enum AnnouncedBet {
+    /// The 17 numbers between 22 and 25 that surround zero (9 chips)
+    VoisinsDuZero,
+
+    /// Tiers du Cylindre, the 12 numbers between 27 and 33 opposite zero (6 chips)
+    Tiers,
+
+    /// The 8 numbers not covered by Voisins du Zéro or Tiers (5 chips)
+    Orphelins,
+
+    /// The 7 numbers closest to zero, from 12 to 15 (4 chips)
+    JeuZero,
+
+    /// A number plus ```count``` pockets either side of it on the wheel, one chip straight up on each (2 x count + 1 chips)
+    Neighbours { number: u8, count: u8 },
+
+    /// Finale en plein: one chip straight up on every number ending in the given digit (3 or 4 chips)
+    FinaleEnPlein(u8),
+
+    /// Finale à cheval: one chip on each split joining the numbers ending in the two digits, or straight up where
+    /// only one of the two numbers exists (e.g. 3/6 covers 3/6, 13/16, 23/26 and 33/36)
+    FinaleACheval(u8, u8),
+}
+
+const VOISINS_DU_ZERO: [(RouletteBetType, u64); 7] = [
+    (RouletteBetType::Basket([0, 2, 3]), 2),
+    (RouletteBetType::Split([4, 7]), 1),
+    (RouletteBetType::Split([12, 15]), 1),
+    (RouletteBetType::Split([18, 21]), 1),
+    (RouletteBetType::Split([19, 22]), 1),
+    (RouletteBetType::Split([32, 35]), 1),
+    (RouletteBetType::Corner([25, 26, 28, 29]), 2),
+];
+
+const TIERS: [(RouletteBetType, u64); 6] = [
+    (RouletteBetType::Split([5, 8]), 1),
+    (RouletteBetType::Split([10, 11]), 1),
+    (RouletteBetType::Split([13, 16]), 1),
+    (RouletteBetType::Split([23, 24]), 1),
+    (RouletteBetType::Split([27, 30]), 1),
+    (RouletteBetType::Split([33, 36]), 1),
+];
+
+const ORPHELINS: [(RouletteBetType, u64); 5] = [
+    (RouletteBetType::Straight(1), 1),
+    (RouletteBetType::Split([6, 9]), 1),
+    (RouletteBetType::Split([14, 17]), 1),
+    (RouletteBetType::Split([17, 20]), 1),
+    (RouletteBetType::Split([31, 34]), 1),
+];
+
+const JEU_ZERO: [(RouletteBetType, u64); 4] = [
+    (RouletteBetType::Split([0, 3]), 1),
+    (RouletteBetType::Split([12, 15]), 1),
+    (RouletteBetType::Straight(26), 1),
+    (RouletteBetType::Split([32, 35]), 1),
+];
+
+impl fmt::Display for AnnouncedBet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnnouncedBet::VoisinsDuZero => write!(f, "Voisins du Zéro"),
+            AnnouncedBet::Tiers => write!(f, "Tiers du Cylindre"),
+            AnnouncedBet::Orphelins => write!(f, "Orphelins"),
+            AnnouncedBet::JeuZero => write!(f, "Jeu Zéro"),
+            AnnouncedBet::Neighbours { number, count } => write!(f, "{} and {} neighbours", Pocket(*number), count),
+            AnnouncedBet::FinaleEnPlein(last) => write!(f, "Finale {}", last),
+            AnnouncedBet::FinaleACheval(first, second) => write!(f, "Finale {}/{}", first, second),
+        }
+    }
+}
+
+impl AnnouncedBet {
+    /// The inside bets making up the announcement on the given wheel, with the number of chips placed on each
+    pub fn layout(&self, variant: WheelVariant) -> Result<Vec<(RouletteBetType, u64)>, PlaceBetError> {
+        let layout = match *self {
+            AnnouncedBet::VoisinsDuZero => Self::section(variant, &VOISINS_DU_ZERO),
+            AnnouncedBet::Tiers => Self::section(variant, &TIERS),
+            AnnouncedBet::Orphelins => Self::section(variant, &ORPHELINS),
+            AnnouncedBet::JeuZero => Self::section(variant, &JEU_ZERO),
+
+            // neighbours follow the wheel order, so they can be played on either wheel
+            AnnouncedBet::Neighbours { number, count } => variant.neighbours(number, count)
+                .map(|numbers| numbers.into_iter().map(|n| (RouletteBetType::Straight(n), 1)).collect()),
+
+            AnnouncedBet::FinaleEnPlein(last) => Self::finale_en_plein(last),
+            AnnouncedBet::FinaleACheval(first, second) => Self::finale_a_cheval(variant, first, second),
+        };
+
+        layout.ok_or(PlaceBetError::InvalidAnnouncedBet(*self, variant))
+    }
+
+    /// Total number of chips needed to place the announcement, or 0 for a finale that cannot be played
+    pub fn chips(&self) -> u64 {
+        match self {
+            AnnouncedBet::Neighbours { count, .. } => *count as u64 * 2 + 1,
+            _ => self.layout(WheelVariant::European)
+                .map(|layout| layout.iter().map(|(_, chips)| chips).sum())
+                .unwrap_or(0),
+        }
+    }
+
+    /// Expands the announcement into the bets to place, using ```chip``` as the value of a single chip.
+    /// Fails with ```PayoutOverflow``` if the wager of a spot doesn't fit in a ```u64```; the bet in the error then
+    /// carries a wager of ```u64::MAX```.
+    pub fn bets(&self, variant: WheelVariant, chip: u64) -> Result<Vec<RouletteBet>, PlaceBetError> {
+        self.layout(variant)?
+            .into_iter()
+            .map(|(bet_type, chips)| match chip.checked_mul(chips) {
+                Some(wager) => Ok(RouletteBet::announced(bet_type, wager, *self)),
+                None => Err(PlaceBetError::PayoutOverflow(RouletteBet::announced(bet_type, u64::MAX, *self))),
+            })
+            .collect()
+    }
+
+    /// The classic racetrack sections are only defined on the European wheel
+    fn section(variant: WheelVariant, section: &[(RouletteBetType, u64)]) -> Option<Vec<(RouletteBetType, u64)>> {
+        match variant {
+            WheelVariant::European => Some(section.to_vec()),
+            WheelVariant::American => None,
+        }
+    }
+
+    fn finale_en_plein(last: u8) -> Option<Vec<(RouletteBetType, u64)>> {
+        if last > 9 {
+            return None;
+        }
+
+        Some((last..=36).step_by(10).map(|n| (RouletteBetType::Straight(n), 1)).collect())
+    }
+
+    /// Every pair of numbers ending in the two digits has to form a split that is legal on the table,
+    /// otherwise the finale cannot be played à cheval.
+    fn finale_a_cheval(variant: WheelVariant, first: u8, second: u8) -> Option<Vec<(RouletteBetType, u64)>> {
+        if first > 9 || second > 9 || first == second {
+            return None;
+        }
+
+        let (low, high) = (first.min(second), first.max(second));
+        let mut layout = Vec::new();
+        for decade in (0..=30).step_by(10) {
+            let bet_type = match (decade + low, decade + high) {
+                (a, b) if b <= 36 => RouletteBetType::Split([a, b]),
+                (a, _) if a <= 36 => RouletteBetType::Straight(a),
+                _ => break,
+            };
+
+            if !Roulette::validate_bet_option(variant, bet_type) {
+                return None;
+            }
+            layout.push((bet_type, 1));
+        }
+
+        Some(layout)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rng::ScriptedSpins;
+    use crate::roulette::Roulette;
+
+    const ALL: [AnnouncedBet; 4] = [AnnouncedBet::VoisinsDuZero, AnnouncedBet::Tiers, AnnouncedBet::Orphelins, AnnouncedBet::JeuZero];
+
+    #[test]
+    fn chip_counts() {
+        assert_eq!(AnnouncedBet::VoisinsDuZero.chips(), 9);
+        assert_eq!(AnnouncedBet::Tiers.chips(), 6);
+        assert_eq!(AnnouncedBet::Orphelins.chips(), 5);
+        assert_eq!(AnnouncedBet::JeuZero.chips(), 4);
+
+        for announced in ALL.iter() {
+            let bets = announced.bets(WheelVariant::European, 5).unwrap();
+            assert_eq!(bets.iter().map(|b| b.wager()).sum::<u64>(), announced.chips() * 5);
+            assert!(bets.iter().all(|b| b.announcement() == Some(*announced)));
+        }
+    }
+
+    #[test]
+    fn sections_cover_the_wheel_once() {
+        // Voisins, Tiers and Orphelins together cover every number exactly once
+        let mut covered = [0; 37];
+        for announced in &ALL[..3] {
+            for (bet_type, _) in announced.layout(WheelVariant::European).unwrap() {
+                let numbers: &[u8] = match bet_type {
+                    RouletteBetType::Straight(ref v) => std::slice::from_ref(v),
+                    RouletteBetType::Split(ref v) => v,
+                    RouletteBetType::Basket(ref v) => v,
+                    RouletteBetType::Corner(ref v) => v,
+                    _ => unreachable!(),
+                };
+                for &n in numbers {
+                    covered[n as usize] += 1;
+                }
+            }
+        }
+
+        // Orphelins covers 17 twice (14/17 and 17/20), everything else is covered once
+        for (n, &count) in covered.iter().enumerate() {
+            assert_eq!(count, if n == 17 { 2 } else { 1 }, "number {}", n);
+        }
+    }
+
+    #[test]
+    fn settles_through_spin() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![0]));
+        let bets = AnnouncedBet::VoisinsDuZero.bets(WheelVariant::European, 1).unwrap();
+
+        let (_, results) = r.spin(&bets).unwrap();
+        let total: u64 = results.iter().map(|r| r.win()).sum();
+        assert_eq!(total, 24);
+        assert_eq!(results[0].bet().announcement(), Some(AnnouncedBet::VoisinsDuZero));
+        assert_eq!(results[0].bet().to_string(), "type: Basket(0, 2, 3), wager: 2 (Voisins du Zéro)");
+    }
+
+    #[test]
+    fn neighbours_through_spin() {
+        let seventeen = AnnouncedBet::Neighbours { number: 17, count: 2 };
+        assert_eq!(seventeen.chips(), 5);
+        assert_eq!(seventeen.to_string(), "17 and 2 neighbours");
+
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![34]));
+        let bets = seventeen.bets(WheelVariant::European, 10).unwrap();
+        let straights: Vec<String> = bets.iter().map(|b| b.bet_type().to_string()).collect();
+        assert_eq!(straights, vec!["Straight(2)", "Straight(25)", "Straight(17)", "Straight(34)", "Straight(6)"]);
+
+        let (_, results) = r.spin(&bets).unwrap();
+        assert_eq!(results.iter().map(|r| r.win()).sum::<u64>(), 360);
+    }
+
+    #[test]
+    fn invalid_neighbours() {
+        assert!(AnnouncedBet::Neighbours { number: 37, count: 2 }.bets(WheelVariant::European, 1).is_err());
+        assert!(AnnouncedBet::Neighbours { number: 17, count: 19 }.bets(WheelVariant::European, 1).is_err());
+        assert!(AnnouncedBet::Neighbours { number: 37, count: 2 }.bets(WheelVariant::American, 1).is_ok());
+    }
+
+    #[test]
+    fn finale_en_plein() {
+        let numbers = |last: u8| -> Vec<String> {
+            AnnouncedBet::FinaleEnPlein(last).bets(WheelVariant::European, 1).unwrap()
+                .iter()
+                .map(|b| b.bet_type().to_string())
+                .collect()
+        };
+
+        assert_eq!(numbers(7), vec!["Straight(7)", "Straight(17)", "Straight(27)"]);
+        assert_eq!(numbers(0), vec!["Straight(0)", "Straight(10)", "Straight(20)", "Straight(30)"]);
+        assert_eq!(AnnouncedBet::FinaleEnPlein(6).chips(), 4);
+        assert_eq!(AnnouncedBet::FinaleEnPlein(9).chips(), 3);
+        assert!(AnnouncedBet::FinaleEnPlein(10).bets(WheelVariant::European, 1).is_err());
+        assert!(AnnouncedBet::FinaleEnPlein(7).bets(WheelVariant::American, 1).is_ok());
+    }
+
+    #[test]
+    fn finale_a_cheval() {
+        let bet_types = |first: u8, second: u8, variant: WheelVariant| -> Option<Vec<String>> {
+            AnnouncedBet::FinaleACheval(first, second).bets(variant, 1).ok()
+                .map(|bets| bets.iter().map(|b| b.bet_type().to_string()).collect())
+        };
+
+        assert_eq!(bet_types(3, 6, WheelVariant::European).unwrap(), vec!["Split(3, 6)", "Split(13, 16)", "Split(23, 26)", "Split(33, 36)"]);
+        assert_eq!(bet_types(7, 4, WheelVariant::European).unwrap(), vec!["Split(4, 7)", "Split(14, 17)", "Split(24, 27)", "Straight(34)"]);
+        assert_eq!(bet_types(0, 3, WheelVariant::European).unwrap(), vec!["Split(0, 3)", "Split(10, 13)", "Split(20, 23)", "Split(30, 33)"]);
+        assert_eq!(AnnouncedBet::FinaleACheval(4, 7).chips(), 4);
+
+        // 21 and 22 sit on different rows, and 0/3 is not a split on a double zero table
+        assert_eq!(bet_types(1, 2, WheelVariant::European), None);
+        assert_eq!(bet_types(0, 3, WheelVariant::American), None);
+        assert_eq!(bet_types(3, 3, WheelVariant::European), None);
+        assert_eq!(AnnouncedBet::FinaleACheval(1, 2).chips(), 0);
+    }
+
+    #[test]
+    fn european_only() {
+        for announced in ALL.iter() {
+            assert!(announced.bets(WheelVariant::American, 1).is_err());
+        }
+    }
+
+    #[test]
+    fn chip_overflow() {
+        // the 0/2/3 basket takes two chips
+        let chip = u64::MAX / 2 + 1;
+        assert_eq!(AnnouncedBet::VoisinsDuZero.bets(WheelVariant::European, chip), Err(PlaceBetError::PayoutOverflow(
+            RouletteBet::announced(RouletteBetType::Basket([0, 2, 3]), u64::MAX, AnnouncedBet::VoisinsDuZero),
+        )));
+        assert!(AnnouncedBet::Tiers.bets(WheelVariant::European, chip).is_ok());
+    }
+}
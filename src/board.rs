@@ -0,0 +1,191 @@
+use crate::roulette::{Roulette, RouletteBetType};
+use crate::wheel::{WheelVariant, DOUBLE_ZERO};
+
+/// A chip position on the inside part of the layout, on a grid of half cells so that the lines between numbers and
+/// their intersections have coordinates too.
+///
+/// ```x``` runs across the three columns: 0 is the street edge (the outer line next to the 1, 4, 7... column), the
+/// columns are at 1, 3 and 5, and 2 and 4 are the lines between them.
+/// ```y``` runs down the table: 0 is the zero box, 1 the line between the zero box and the first row, then the rows
+/// of numbers are at the even coordinates (1-2-3 at 2, 4-5-6 at 4, ... 34-35-36 at 24) with the lines between
+/// them at the odd ones.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BoardPoint {
+    pub x: u8,
+    pub y: u8,
+}
+
+/// Number of grid positions across the layout
+pub const BOARD_WIDTH: u8 = 6;
+
+/// Number of grid positions down the layout
+pub const BOARD_HEIGHT: u8 = 25;
+
+/// Chip positions in the zero box and on the zero line. Where several positions give the same bet, the first one
+/// is where it is drawn.
+const EUROPEAN_ZERO_AREA: [((u8, u8), RouletteBetType); 9] = [
+    ((3, 0), RouletteBetType::Straight(0)),
+    ((1, 0), RouletteBetType::Straight(0)),
+    ((5, 0), RouletteBetType::Straight(0)),
+    ((0, 1), RouletteBetType::Topline([0, 1, 2, 3])),
+    ((1, 1), RouletteBetType::Split([0, 1])),
+    ((2, 1), RouletteBetType::Basket([0, 1, 2])),
+    ((3, 1), RouletteBetType::Split([0, 2])),
+    ((4, 1), RouletteBetType::Basket([0, 2, 3])),
+    ((5, 1), RouletteBetType::Split([0, 3])),
+];
+
+/// On a double zero layout 0 sits above the first column and 00 above the third, the 0/00 split between them
+const AMERICAN_ZERO_AREA: [((u8, u8), RouletteBetType); 9] = [
+    ((1, 0), RouletteBetType::Straight(0)),
+    ((3, 0), RouletteBetType::Split([0, DOUBLE_ZERO])),
+    ((5, 0), RouletteBetType::Straight(DOUBLE_ZERO)),
+    ((0, 1), RouletteBetType::Fiveline([0, 1, 2, 3, DOUBLE_ZERO])),
+    ((1, 1), RouletteBetType::Split([0, 1])),
+    ((2, 1), RouletteBetType::Basket([0, 1, 2])),
+    ((3, 1), RouletteBetType::Basket([0, 2, DOUBLE_ZERO])),
+    ((4, 1), RouletteBetType::Basket([2, 3, DOUBLE_ZERO])),
+    ((5, 1), RouletteBetType::Split([3, DOUBLE_ZERO])),
+];
+
+impl BoardPoint {
+    pub fn new(x: u8, y: u8) -> Self {
+        Self {
+            x,
+            y,
+        }
+    }
+
+    /// The position of a number's box (1 to 36)
+    pub fn of_number(number: u8) -> Option<Self> {
+        if !(1..=36).contains(&number) {
+            return None;
+        }
+        let row = (number - 1) / 3;
+        let column = (number - 1) % 3;
+        Some(Self::new(2 * column + 1, 2 * row + 2))
+    }
+
+    /// The bet made by a chip placed here, if the position is a playable one on the given wheel
+    pub fn bet_type(&self, variant: WheelVariant) -> Option<RouletteBetType> {
+        let (x, y) = (self.x, self.y);
+        if x >= BOARD_WIDTH || y >= BOARD_HEIGHT {
+            return None;
+        }
+
+        if y <= 1 {
+            return zero_area(variant)
+                .iter()
+                .find(|(point, _)| *point == (x, y))
+                .map(|(_, bet_type)| *bet_type);
+        }
+
+        // the number at the top left of the position: rows are at even y, columns at odd x
+        let row = (y - 2) / 2;
+        let first = 3 * row + 1;
+
+        Some(match (x, y % 2 == 0) {
+            (0, true) => RouletteBetType::Street([first, first + 1, first + 2]),
+            (0, false) => RouletteBetType::Doubleline([first, first + 1, first + 2, first + 3, first + 4, first + 5]),
+            (x, true) if x % 2 == 1 => RouletteBetType::Straight(first + (x - 1) / 2),
+            (x, true) => {
+                let left = first + (x - 2) / 2;
+                RouletteBetType::Split([left, left + 1])
+            },
+            (x, false) if x % 2 == 1 => {
+                let top = first + (x - 1) / 2;
+                RouletteBetType::Split([top, top + 3])
+            },
+            (x, false) => {
+                let left = first + (x - 2) / 2;
+                RouletteBetType::Corner([left, left + 1, left + 3, left + 4])
+            },
+        })
+    }
+
+    /// Where a chip for the given inside bet is drawn. Outside bets, and bets that can't be played on the wheel,
+    /// have no position on the grid.
+    pub fn for_bet(variant: WheelVariant, bet_type: RouletteBetType) -> Option<Self> {
+        let bet_type = bet_type.normalized();
+        if !Roulette::validate_bet_option(variant, bet_type) {
+            return None;
+        }
+
+        if let Some(((x, y), _)) = zero_area(variant).iter().find(|(_, zero_bet)| *zero_bet == bet_type) {
+            return Some(Self::new(*x, *y));
+        }
+
+        match bet_type {
+            RouletteBetType::Straight(v) => Self::of_number(v),
+            RouletteBetType::Split(v) => Self::between(v[0], v[1]),
+            RouletteBetType::Corner(v) => Self::between(v[0], v[3]),
+            RouletteBetType::Street(v) => Self::of_number(v[0]).map(|p| Self::new(0, p.y)),
+            RouletteBetType::Doubleline(v) => Self::of_number(v[0]).map(|p| Self::new(0, p.y + 1)),
+            _ => None,
+        }
+    }
+
+    /// The point halfway between the boxes of two numbers
+    fn between(a: u8, b: u8) -> Option<Self> {
+        let (a, b) = (Self::of_number(a)?, Self::of_number(b)?);
+        Some(Self::new((a.x + b.x) / 2, (a.y + b.y) / 2))
+    }
+}
+
+fn zero_area(variant: WheelVariant) -> &'static [((u8, u8), RouletteBetType)] {
+    match variant {
+        WheelVariant::European => &EUROPEAN_ZERO_AREA,
+        WheelVariant::American => &AMERICAN_ZERO_AREA,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::outside::Dozen;
+
+    #[test]
+    fn positions_resolve_to_bets() {
+        let european = |x, y| BoardPoint::new(x, y).bet_type(WheelVariant::European);
+
+        assert_eq!(european(3, 2), Some(RouletteBetType::Straight(2)));
+        assert_eq!(european(2, 2), Some(RouletteBetType::Split([1, 2])));
+        assert_eq!(european(5, 3), Some(RouletteBetType::Split([3, 6])));
+        assert_eq!(european(4, 3), Some(RouletteBetType::Corner([2, 3, 5, 6])));
+        assert_eq!(european(0, 24), Some(RouletteBetType::Street([34, 35, 36])));
+        assert_eq!(european(0, 3), Some(RouletteBetType::Doubleline([1, 2, 3, 4, 5, 6])));
+        assert_eq!(european(0, 1), Some(RouletteBetType::Topline([0, 1, 2, 3])));
+        assert_eq!(european(4, 1), Some(RouletteBetType::Basket([0, 2, 3])));
+        assert_eq!(european(5, 0), Some(RouletteBetType::Straight(0)));
+        assert_eq!(european(0, 0), None);
+        assert_eq!(european(6, 2), None);
+        assert_eq!(european(1, 25), None);
+
+        let american = |x, y| BoardPoint::new(x, y).bet_type(WheelVariant::American);
+        assert_eq!(american(3, 0), Some(RouletteBetType::Split([0, DOUBLE_ZERO])));
+        assert_eq!(american(0, 1), Some(RouletteBetType::Fiveline([0, 1, 2, 3, DOUBLE_ZERO])));
+        assert_eq!(american(5, 1), Some(RouletteBetType::Split([3, DOUBLE_ZERO])));
+    }
+
+    #[test]
+    fn bets_map_back_to_their_position() {
+        for &variant in &[WheelVariant::European, WheelVariant::American] {
+            for y in 0..BOARD_HEIGHT {
+                for x in 0..BOARD_WIDTH {
+                    if let Some(bet_type) = BoardPoint::new(x, y).bet_type(variant) {
+                        let drawn = BoardPoint::for_bet(variant, bet_type).unwrap();
+                        assert_eq!(drawn.bet_type(variant), Some(bet_type));
+                        if y > 0 {
+                            assert_eq!(drawn, BoardPoint::new(x, y), "{}", bet_type);
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(BoardPoint::for_bet(WheelVariant::European, RouletteBetType::Straight(0)), Some(BoardPoint::new(3, 0)));
+        assert_eq!(BoardPoint::for_bet(WheelVariant::European, RouletteBetType::Split([11, 10])), Some(BoardPoint::new(2, 8)));
+        assert_eq!(BoardPoint::for_bet(WheelVariant::European, RouletteBetType::Dozens(Dozen::First)), None);
+        assert_eq!(BoardPoint::for_bet(WheelVariant::American, RouletteBetType::Topline([0, 1, 2, 3])), None);
+    }
+}
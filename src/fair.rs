@@ -0,0 +1,190 @@
+//! Provably fair spins using a commit-reveal scheme.
+//!
+//! Before a round the house publishes the commitment ```SHA-256(server_seed)```. The player picks a client seed, and
+//! every spin made with that server seed uses the next nonce (starting at 0). The winning pocket is taken from
+//! ```HMAC-SHA256(key = server_seed, message = "<client_seed>:<nonce>:<round>")```, read as big endian u32 chunks.
+//! Chunks that fall in the incomplete top range of the u32 space are rejected so every pocket is equally likely;
+//! if all 8 chunks are rejected, ```round``` is incremented and a new HMAC is computed.
+//!
+//! Once the server seed is rotated out it is revealed, and anyone can check the commitment with
+//! ```verify_commitment``` and recompute each spin with ```verify_spin```.
+
+use hmac_sha256::{Hash, HMAC};
+use rand::prelude::*;
+use crate::rng::SpinRng;
+use crate::roulette::Roulette;
+use crate::wheel::WheelVariant;
+
+/// Returns the commitment published for ```server_seed```
+pub fn commitment(server_seed: &[u8]) -> [u8; 32] {
+    Hash::hash(server_seed)
+}
+
+/// Checks that a revealed server seed matches the commitment published before the round
+pub fn verify_commitment(server_seed: &[u8], commitment: &[u8; 32]) -> bool {
+    Hash::hash(server_seed) == *commitment
+}
+
+/// Recomputes the winning pocket of a European (single zero) spin
+pub fn verify_spin(server_seed: &[u8], client_seed: &str, nonce: u64) -> u8 {
+    verify_spin_on(WheelVariant::European, server_seed, client_seed, nonce)
+}
+
+/// Recomputes the winning pocket of a spin on the given wheel variant
+pub fn verify_spin_on(variant: WheelVariant, server_seed: &[u8], client_seed: &str, nonce: u64) -> u8 {
+    pocket_index(variant.pocket_count(), server_seed, client_seed, nonce) as u8
+}
+
+/// Hex encoding of a seed or commitment, as shown to players
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn pocket_index(pockets: usize, server_seed: &[u8], client_seed: &str, nonce: u64) -> usize {
+    let pockets = pockets as u64;
+    // Largest multiple of ```pockets``` that fits in a u32. Anything at or above it would favour the low pockets.
+    let zone = (1u64 << 32) / pockets * pockets;
+
+    for round in 0u64.. {
+        let mac = HMAC::mac(format!("{}:{}:{}", client_seed, nonce, round), server_seed);
+        for chunk in mac.chunks(4) {
+            let value = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as u64;
+            if value < zone {
+                return (value % pockets) as usize;
+            }
+        }
+    }
+
+    unreachable!()
+}
+
+/// A ```SpinRng``` drawing provably fair pockets. Publish ```commitment()``` before the round, let the player set
+/// their client seed, and ```rotate()``` the server seed to reveal it once the round is over.
+#[derive(Debug, Clone)]
+pub struct FairSpins {
+    server_seed: [u8; 32],
+    client_seed: String,
+    nonce: u64,
+}
+
+impl Default for FairSpins {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FairSpins {
+    /// Starts with a random server seed and an empty client seed
+    pub fn new() -> Self {
+        Self::with_server_seed(Self::generate_seed())
+    }
+
+    pub fn with_server_seed(server_seed: [u8; 32]) -> Self {
+        Self {
+            server_seed,
+            client_seed: String::new(),
+            nonce: 0,
+        }
+    }
+
+    /// The hash of the current server seed, to publish before any spin is made with it
+    pub fn commitment(&self) -> [u8; 32] {
+        commitment(&self.server_seed)
+    }
+
+    pub fn client_seed(&self) -> &str {
+        &self.client_seed
+    }
+
+    pub fn set_client_seed(&mut self, client_seed: &str) {
+        self.client_seed = client_seed.to_string();
+    }
+
+    /// The nonce the next spin will use
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Reveals the current server seed and replaces it with a fresh one, resetting the nonce
+    pub fn rotate(&mut self) -> [u8; 32] {
+        let revealed = self.server_seed;
+        self.server_seed = Self::generate_seed();
+        self.nonce = 0;
+        revealed
+    }
+
+    fn generate_seed() -> [u8; 32] {
+        let mut seed = [0u8; 32];
+        thread_rng().fill(&mut seed);
+        seed
+    }
+}
+
+impl SpinRng for FairSpins {
+    fn pick_pocket(&mut self, pockets: usize) -> usize {
+        let pocket = pocket_index(pockets, &self.server_seed, &self.client_seed, self.nonce);
+        self.nonce += 1;
+        pocket
+    }
+
+    /// The published commitment, client seed and nonce: all a player needs to verify the spin once the seed is revealed
+    fn provenance(&self) -> String {
+        format!("fair commitment={} client_seed={} nonce={}", to_hex(&self.commitment()), self.client_seed, self.nonce)
+    }
+}
+
+impl Roulette<FairSpins> {
+    /// Creates a roulette in provably fair mode with a random server seed
+    pub fn provably_fair(variant: WheelVariant) -> Self {
+        Self::with_rng(variant, FairSpins::new())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn spins_can_be_verified() {
+        for &variant in &[WheelVariant::European, WheelVariant::American] {
+            let mut r = Roulette::provably_fair(variant);
+            r.rng_mut().set_client_seed("lucky player");
+            let published = r.rng().commitment();
+
+            for _ in 0..20 {
+                r.spin(&[]).unwrap();
+            }
+
+            let server_seed = r.rng_mut().rotate();
+            assert!(verify_commitment(&server_seed, &published));
+            assert_eq!(r.rng().nonce(), 0);
+
+            for (nonce, &number) in r.history().iter().enumerate() {
+                assert_eq!(verify_spin_on(variant, &server_seed, "lucky player", nonce as u64), number);
+            }
+
+            let record = r.spin_log().get(20).unwrap();
+            assert_eq!(record.rng, format!("fair commitment={} client_seed=lucky player nonce=19", to_hex(&published)));
+        }
+    }
+
+    #[test]
+    fn verify_spin_is_stable() {
+        let server_seed = [7u8; 32];
+
+        assert_eq!(verify_spin(&server_seed, "client", 0), verify_spin(&server_seed, "client", 0));
+        for nonce in 0..1000 {
+            assert!(verify_spin(&server_seed, "client", nonce) <= 36);
+        }
+
+        let first: Vec<u8> = (0..10).map(|n| verify_spin(&server_seed, "client", n)).collect();
+        let other: Vec<u8> = (0..10).map(|n| verify_spin(&server_seed, "other client", n)).collect();
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn commitment_is_sha256() {
+        assert_eq!(to_hex(&commitment(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert!(!verify_commitment(b"abd", &commitment(b"abc")));
+    }
+}
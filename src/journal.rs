@@ -0,0 +1,646 @@
+//! Append-only on-disk journal of spin records.
+//!
+//! The journal is a directory of segment files named after the id of the first spin they hold
+//! (```00000000000000000001.journal```). Each record is written as
+//!
+//! ```text
+//! [payload length: u32 LE][CRC-32 of the payload: u32 LE][payload]
+//! ```
+//!
+//! A new segment is started once the current one grows past the segment size. When a journal is opened, every
+//! segment is read back and checked; a record cut short or failing its checksum at the end of the last segment is
+//! what a crash in the middle of a write leaves behind, so the tail is truncated at the last good record. Damage
+//! anywhere else, and records that pass their checksum but can't be decoded, are reported as ```InvalidData```.
+
+use std::convert::TryFrom;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+use crate::announced::AnnouncedBet;
+use crate::money::Currency;
+use crate::record::{SpinLog, SpinRecord};
+use crate::roulette::{RouletteBet, RouletteBetType};
+use crate::wheel::WheelVariant;
+
+const EXTENSION: &str = "journal";
+const HEADER_SIZE: usize = 8;
+
+/// Segments roll over once they reach 4 MiB
+pub const DEFAULT_SEGMENT_SIZE: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug)]
+pub struct Journal {
+    dir: PathBuf,
+    segment_size: u64,
+
+    /// First spin id of each segment, oldest first. The last one is the segment being appended to.
+    segments: Vec<u64>,
+    current: Option<File>,
+    current_size: u64,
+    next_id: u64,
+}
+
+impl Journal {
+    /// Opens (or creates) the journal in ```dir```, returning it along with every record it holds, oldest first.
+    /// A torn record at the end of the last segment is cut off.
+    pub fn open<P: AsRef<Path>>(dir: P) -> io::Result<(Self, Vec<SpinRecord>)> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let mut segments = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            if let Some(first_id) = path.file_stem().and_then(|s| s.to_str()).and_then(|s| s.parse::<u64>().ok()) {
+                segments.push(first_id);
+            }
+        }
+        segments.sort_unstable();
+
+        let mut journal = Self {
+            dir,
+            segment_size: DEFAULT_SEGMENT_SIZE,
+            segments,
+            current: None,
+            current_size: 0,
+            next_id: 1,
+        };
+
+        let mut records = Vec::new();
+        let count = journal.segments.len();
+        for (index, &first_id) in journal.segments.iter().enumerate() {
+            let path = journal.segment_path(first_id);
+            let bytes = fs::read(&path)?;
+            let (mut segment_records, good) = read_segment(&bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{} in {}", e, path.display())))?;
+
+            if good < bytes.len() {
+                if index + 1 < count {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, format!("corrupt record in {}", path.display())));
+                }
+                // torn write at the end of the journal
+                let file = OpenOptions::new().write(true).open(&path)?;
+                file.set_len(good as u64)?;
+                file.sync_all()?;
+            }
+
+            if index + 1 == count {
+                journal.current_size = good as u64;
+            }
+            records.append(&mut segment_records);
+        }
+
+        journal.next_id = match records.last() {
+            Some(record) => record.id + 1,
+            None => journal.segments.last().copied().unwrap_or(1),
+        };
+
+        Ok((journal, records))
+    }
+
+    pub fn segment_size(&self) -> u64 {
+        self.segment_size
+    }
+
+    /// Size, in bytes, past which a new segment is started
+    pub fn set_segment_size(&mut self, segment_size: u64) {
+        self.segment_size = segment_size;
+    }
+
+    /// Number of segment files on disk
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// The id of the next record expected
+    pub fn next_id(&self) -> u64 {
+        self.next_id
+    }
+
+    /// Appends a record and flushes it to disk. Records must be appended in id order.
+    pub fn append(&mut self, record: &SpinRecord) -> io::Result<()> {
+        if record.id < self.next_id {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("spin {} is already in the journal", record.id)));
+        }
+
+        let payload = encode_record(record);
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + payload.len());
+        bytes.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+
+        if self.segments.is_empty() || self.current_size >= self.segment_size {
+            self.start_segment(record.id)?;
+        }
+
+        if self.current.is_none() {
+            let path = self.segment_path(*self.segments.last().unwrap());
+            self.current = Some(OpenOptions::new().append(true).open(path)?);
+        }
+        let file = self.current.as_mut().unwrap();
+        file.write_all(&bytes)?;
+        file.sync_data()?;
+
+        self.current_size += bytes.len() as u64;
+        self.next_id = record.id + 1;
+        Ok(())
+    }
+
+    /// Appends the records of ```log``` not yet in the journal. Returns the number of records written.
+    pub fn sync(&mut self, log: &SpinLog) -> io::Result<usize> {
+        let next_id = self.next_id;
+        let mut written = 0;
+        for record in log.records().filter(|record| record.id >= next_id) {
+            self.append(record)?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    /// Deletes the segments only holding spins older than ```before_id```. The segment being appended to is
+    /// always kept. Returns the number of segments removed.
+    pub fn compact(&mut self, before_id: u64) -> io::Result<usize> {
+        let mut removed = 0;
+        while self.segments.len() > 1 && self.segments[1] <= before_id {
+            fs::remove_file(self.segment_path(self.segments[0]))?;
+            self.segments.remove(0);
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    fn start_segment(&mut self, first_id: u64) -> io::Result<()> {
+        let file = OpenOptions::new().create_new(true).append(true).open(self.segment_path(first_id))?;
+        self.segments.push(first_id);
+        self.current = Some(file);
+        self.current_size = 0;
+        Ok(())
+    }
+
+    fn segment_path(&self, first_id: u64) -> PathBuf {
+        self.dir.join(format!("{:020}.{}", first_id, EXTENSION))
+    }
+}
+
+/// Reads the records of a segment, stopping at the first one that is incomplete or fails its checksum.
+/// Also returns the length of the valid part of the segment. A record with a good checksum that can't be decoded
+/// was written whole, so it is an error rather than a torn write.
+fn read_segment(bytes: &[u8]) -> io::Result<(Vec<SpinRecord>, usize)> {
+    let mut records = Vec::new();
+    let mut offset = 0;
+
+    while bytes.len() - offset >= HEADER_SIZE {
+        let len = u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]]) as usize;
+        let crc = u32::from_le_bytes([bytes[offset + 4], bytes[offset + 5], bytes[offset + 6], bytes[offset + 7]]);
+        let start = offset + HEADER_SIZE;
+
+        let payload = match bytes.get(start..start + len) {
+            Some(payload) if crc32fast::hash(payload) == crc => payload,
+            _ => break,
+        };
+        records.push(decode_record(payload)?);
+        offset = start + len;
+    }
+
+    Ok((records, offset))
+}
+
+fn encode_record(record: &SpinRecord) -> Vec<u8> {
+    let since_epoch = record.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&record.id.to_le_bytes());
+    buf.extend_from_slice(&since_epoch.as_secs().to_le_bytes());
+    buf.extend_from_slice(&since_epoch.subsec_nanos().to_le_bytes());
+    buf.push(match record.variant {
+        WheelVariant::European => 0,
+        WheelVariant::American => 1,
+    });
+    buf.extend_from_slice(&(record.rng.len() as u32).to_le_bytes());
+    buf.extend_from_slice(record.rng.as_bytes());
+    buf.push(record.number);
+    buf.extend_from_slice(&(record.bets.len() as u32).to_le_bytes());
+    for bet in &record.bets {
+        encode_bet(&mut buf, bet);
+    }
+    buf.extend_from_slice(&record.total_wagered.to_le_bytes());
+    buf.extend_from_slice(&record.total_paid.to_le_bytes());
+    buf.extend_from_slice(&record.house_net.to_le_bytes());
+    buf
+}
+
+/// Set on the bet type tag of bets that are not in chips
+const CURRENCY_FLAG: u8 = 0x80;
+
+fn encode_bet(buf: &mut Vec<u8>, bet: &RouletteBet) {
+    let bet_type = bet.bet_type();
+    let selector: u8;
+    let (tag, numbers): (u8, &[u8]) = match &bet_type {
+        RouletteBetType::Straight(v) => (0, std::slice::from_ref(v)),
+        RouletteBetType::Split(v) => (1, v),
+        RouletteBetType::Street(v) => (2, v),
+        RouletteBetType::Basket(v) => (3, v),
+        RouletteBetType::Topline(v) => (4, v),
+        RouletteBetType::Fiveline(v) => (5, v),
+        RouletteBetType::Corner(v) => (6, v),
+        RouletteBetType::Doubleline(v) => (7, v),
+        RouletteBetType::Dozens(v) => {
+            selector = u8::from(*v);
+            (8, std::slice::from_ref(&selector))
+        },
+        RouletteBetType::Columns(v) => {
+            selector = u8::from(*v);
+            (9, std::slice::from_ref(&selector))
+        },
+        RouletteBetType::EvenOdd(v) => {
+            selector = u8::from(*v);
+            (10, std::slice::from_ref(&selector))
+        },
+        RouletteBetType::Highlow(v) => {
+            selector = u8::from(*v);
+            (11, std::slice::from_ref(&selector))
+        },
+        RouletteBetType::Redblack(v) => {
+            selector = u8::from(*v);
+            (12, std::slice::from_ref(&selector))
+        },
+    };
+    // bets in chips are written as they were before bets had a currency, the others flag their tag and follow it
+    // with the currency
+    if bet.currency().is_chips() {
+        buf.push(tag);
+    } else {
+        buf.extend_from_slice(&[tag | CURRENCY_FLAG, currency_code(bet.currency())]);
+    }
+    buf.extend_from_slice(numbers);
+    buf.extend_from_slice(&bet.wager().to_le_bytes());
+
+    match bet.announcement() {
+        None => buf.push(0),
+        Some(AnnouncedBet::VoisinsDuZero) => buf.push(1),
+        Some(AnnouncedBet::Tiers) => buf.push(2),
+        Some(AnnouncedBet::Orphelins) => buf.push(3),
+        Some(AnnouncedBet::JeuZero) => buf.push(4),
+        Some(AnnouncedBet::Neighbours { number, count }) => buf.extend_from_slice(&[5, number, count]),
+        Some(AnnouncedBet::FinaleEnPlein(digit)) => buf.extend_from_slice(&[6, digit]),
+        Some(AnnouncedBet::FinaleACheval(a, b)) => buf.extend_from_slice(&[7, a, b]),
+    }
+}
+
+fn invalid(what: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid {} in journal record", what))
+}
+
+fn decode_record(mut payload: &[u8]) -> io::Result<SpinRecord> {
+    let buf = &mut payload;
+
+    let id = read_u64(buf)?;
+    let secs = read_u64(buf)?;
+    let nanos = read_u32(buf)?;
+    let timestamp = Some(nanos)
+        .filter(|&nanos| nanos < 1_000_000_000)
+        .and_then(|nanos| UNIX_EPOCH.checked_add(Duration::new(secs, nanos)))
+        .ok_or_else(|| invalid("timestamp"))?;
+    let variant = match read_u8(buf)? {
+        0 => WheelVariant::European,
+        1 => WheelVariant::American,
+        _ => return Err(invalid("wheel variant")),
+    };
+    let rng_len = read_u32(buf)? as usize;
+    let rng = String::from_utf8(read_bytes(buf, rng_len)?.to_vec()).map_err(|_| invalid("rng provenance"))?;
+    let number = read_u8(buf)?;
+
+    let bet_count = read_u32(buf)? as usize;
+    let mut bets = Vec::with_capacity(bet_count.min(buf.len()));
+    for _ in 0..bet_count {
+        bets.push(decode_bet(buf)?);
+    }
+
+    let record = SpinRecord {
+        id,
+        timestamp,
+        variant,
+        rng,
+        number,
+        bets,
+        total_wagered: read_u64(buf)?,
+        total_paid: read_u64(buf)?,
+        house_net: read_u64(buf)? as i64,
+    };
+
+    if buf.is_empty() {
+        Ok(record)
+    } else {
+        Err(invalid("record length"))
+    }
+}
+
+fn decode_bet(buf: &mut &[u8]) -> io::Result<RouletteBet> {
+    fn numbers<const N: usize>(buf: &mut &[u8]) -> io::Result<[u8; N]> {
+        let mut v = [0u8; N];
+        v.copy_from_slice(read_bytes(buf, N)?);
+        Ok(v)
+    }
+
+    fn selector<T: TryFrom<u8>>(buf: &mut &[u8]) -> io::Result<T> {
+        T::try_from(read_u8(buf)?).map_err(|_| invalid("outside bet selector"))
+    }
+
+    let tag = read_u8(buf)?;
+    let currency = if tag & CURRENCY_FLAG != 0 {
+        currency_from_code(read_u8(buf)?)?
+    } else {
+        Currency::Chips
+    };
+
+    let bet_type = match tag & !CURRENCY_FLAG {
+        0 => RouletteBetType::Straight(read_u8(buf)?),
+        1 => RouletteBetType::Split(numbers(buf)?),
+        2 => RouletteBetType::Street(numbers(buf)?),
+        3 => RouletteBetType::Basket(numbers(buf)?),
+        4 => RouletteBetType::Topline(numbers(buf)?),
+        5 => RouletteBetType::Fiveline(numbers(buf)?),
+        6 => RouletteBetType::Corner(numbers(buf)?),
+        7 => RouletteBetType::Doubleline(numbers(buf)?),
+        8 => RouletteBetType::Dozens(selector(buf)?),
+        9 => RouletteBetType::Columns(selector(buf)?),
+        10 => RouletteBetType::EvenOdd(selector(buf)?),
+        11 => RouletteBetType::Highlow(selector(buf)?),
+        12 => RouletteBetType::Redblack(selector(buf)?),
+        _ => return Err(invalid("bet type")),
+    };
+    let wager = read_u64(buf)?;
+
+    let announcement = match read_u8(buf)? {
+        0 => None,
+        1 => Some(AnnouncedBet::VoisinsDuZero),
+        2 => Some(AnnouncedBet::Tiers),
+        3 => Some(AnnouncedBet::Orphelins),
+        4 => Some(AnnouncedBet::JeuZero),
+        5 => Some(AnnouncedBet::Neighbours { number: read_u8(buf)?, count: read_u8(buf)? }),
+        6 => Some(AnnouncedBet::FinaleEnPlein(read_u8(buf)?)),
+        7 => Some(AnnouncedBet::FinaleACheval(read_u8(buf)?, read_u8(buf)?)),
+        _ => return Err(invalid("announced bet")),
+    };
+
+    let bet = match announcement {
+        Some(announcement) => RouletteBet::announced(bet_type, wager, announcement),
+        None => RouletteBet::new(bet_type, wager),
+    };
+    Ok(bet.in_currency(currency))
+}
+
+fn currency_code(currency: Currency) -> u8 {
+    match currency {
+        Currency::Chips => 0,
+        Currency::Eur => 1,
+        Currency::Gbp => 2,
+        Currency::Usd => 3,
+        Currency::Btc => 4,
+    }
+}
+
+fn currency_from_code(code: u8) -> io::Result<Currency> {
+    match code {
+        0 => Ok(Currency::Chips),
+        1 => Ok(Currency::Eur),
+        2 => Ok(Currency::Gbp),
+        3 => Ok(Currency::Usd),
+        4 => Ok(Currency::Btc),
+        _ => Err(invalid("currency")),
+    }
+}
+
+fn read_bytes<'a>(buf: &mut &'a [u8], len: usize) -> io::Result<&'a [u8]> {
+    if buf.len() < len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+    let (bytes, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(bytes)
+}
+
+fn read_u8(buf: &mut &[u8]) -> io::Result<u8> {
+    let mut v = [0u8; 1];
+    buf.read_exact(&mut v)?;
+    Ok(v[0])
+}
+
+fn read_u32(buf: &mut &[u8]) -> io::Result<u32> {
+    let mut v = [0u8; 4];
+    buf.read_exact(&mut v)?;
+    Ok(u32::from_le_bytes(v))
+}
+
+fn read_u64(buf: &mut &[u8]) -> io::Result<u64> {
+    let mut v = [0u8; 8];
+    buf.read_exact(&mut v)?;
+    Ok(u64::from_le_bytes(v))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::SystemTime;
+    use crate::limits::TableLimits;
+    use crate::money::Money;
+    use crate::outside::RedBlack;
+    use crate::rng::ScriptedSpins;
+    use crate::roulette::Roulette;
+
+    /// A fresh directory per test, removed when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+            let dir = std::env::temp_dir().join(format!("roulette-journal-{}-{}-{}", name, std::process::id(), nanos));
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn table() -> Roulette<ScriptedSpins> {
+        Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![0, 17, 32, 5]))
+    }
+
+    fn bets() -> Vec<RouletteBet> {
+        let mut bets = vec![
+            RouletteBet::new(RouletteBetType::Split([16, 17]), 10),
+            RouletteBet::new(RouletteBetType::Doubleline([1, 2, 3, 4, 5, 6]), 20),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 30),
+        ];
+        bets.extend(AnnouncedBet::Neighbours { number: 17, count: 2 }.bets(WheelVariant::European, 5).unwrap());
+        bets
+    }
+
+    #[test]
+    fn reload_after_restart() {
+        let dir = TempDir::new("reload");
+        let mut r = table();
+        let bets = bets();
+
+        {
+            let (mut journal, records) = Journal::open(&dir.0).unwrap();
+            assert!(records.is_empty());
+            for _ in 0..5 {
+                r.spin(&bets).unwrap();
+                assert_eq!(journal.sync(r.spin_log()).unwrap(), 1);
+            }
+        }
+
+        let (journal, records) = Journal::open(&dir.0).unwrap();
+        assert_eq!(journal.next_id(), 6);
+        assert_eq!(records, r.spin_log().records().cloned().collect::<Vec<_>>());
+
+        let mut restored = table();
+        restored.restore(records);
+        assert_eq!(restored.history(), r.history());
+        restored.spin(&[]).unwrap();
+        assert_eq!(restored.spin_log().last().unwrap().id, 6);
+    }
+
+    #[test]
+    fn torn_tail_is_cut() {
+        let dir = TempDir::new("torn");
+        let mut r = table();
+
+        let (mut journal, _) = Journal::open(&dir.0).unwrap();
+        for _ in 0..3 {
+            r.spin(&bets()).unwrap();
+        }
+        journal.sync(r.spin_log()).unwrap();
+        drop(journal);
+
+        // simulate a crash half way through writing a fourth record
+        let path = dir.0.join(format!("{:020}.journal", 1));
+        let good_len = fs::metadata(&path).unwrap().len();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[200, 0, 0, 0, 1, 2, 3, 4, 5, 6]).unwrap();
+        drop(file);
+
+        let (mut journal, records) = Journal::open(&dir.0).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(fs::metadata(&path).unwrap().len(), good_len);
+
+        r.spin(&[]).unwrap();
+        journal.sync(r.spin_log()).unwrap();
+        let (_, records) = Journal::open(&dir.0).unwrap();
+        assert_eq!(records.len(), 4);
+    }
+
+    #[test]
+    fn undecodable_tail_is_kept() {
+        let dir = TempDir::new("undecodable");
+        let mut r = table();
+
+        let (mut journal, _) = Journal::open(&dir.0).unwrap();
+        r.spin(&bets()).unwrap();
+        journal.sync(r.spin_log()).unwrap();
+        drop(journal);
+
+        // a whole record, checksum included, that this version can't decode
+        let path = dir.0.join(format!("{:020}.journal", 1));
+        let payload = [0xffu8; 12];
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&(payload.len() as u32).to_le_bytes()).unwrap();
+        file.write_all(&crc32fast::hash(&payload).to_le_bytes()).unwrap();
+        file.write_all(&payload).unwrap();
+        drop(file);
+        let len = fs::metadata(&path).unwrap().len();
+
+        assert_eq!(Journal::open(&dir.0).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(fs::metadata(&path).unwrap().len(), len);
+    }
+
+    #[test]
+    fn timestamps_out_of_range() {
+        let mut r = table();
+        r.spin(&bets()).unwrap();
+        let payload = encode_record(r.spin_log().last().unwrap());
+        assert!(decode_record(&payload).is_ok());
+
+        let mut far = payload.clone();
+        far[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(decode_record(&far).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        let mut carry = payload;
+        carry[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        carry[16..20].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(decode_record(&carry).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn corruption_before_the_tail() {
+        let dir = TempDir::new("corrupt");
+        let mut r = table();
+
+        let (mut journal, _) = Journal::open(&dir.0).unwrap();
+        journal.set_segment_size(1);
+        for _ in 0..3 {
+            r.spin(&bets()).unwrap();
+            journal.sync(r.spin_log()).unwrap();
+        }
+        assert_eq!(journal.segment_count(), 3);
+        drop(journal);
+
+        let path = dir.0.join(format!("{:020}.journal", 1));
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[20] ^= 0xff;
+        fs::write(&path, bytes).unwrap();
+
+        assert_eq!(Journal::open(&dir.0).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn compaction() {
+        let dir = TempDir::new("compact");
+        let mut r = table();
+
+        let (mut journal, _) = Journal::open(&dir.0).unwrap();
+        journal.set_segment_size(1);
+        for _ in 0..4 {
+            r.spin(&[]).unwrap();
+        }
+        journal.sync(r.spin_log()).unwrap();
+        assert_eq!(journal.segment_count(), 4);
+
+        assert_eq!(journal.compact(3).unwrap(), 2);
+        assert_eq!(journal.compact(100).unwrap(), 1);
+        assert_eq!(journal.segment_count(), 1);
+        drop(journal);
+
+        let (journal, records) = Journal::open(&dir.0).unwrap();
+        assert_eq!(records.iter().map(|r| r.id).collect::<Vec<_>>(), vec![4]);
+        assert_eq!(journal.next_id(), 5);
+    }
+
+    #[test]
+    fn bets_keep_their_currency() {
+        let dir = TempDir::new("currency");
+        let mut r = table();
+        let mut limits = TableLimits::default();
+        limits.set_currency(Currency::Btc);
+        r.set_limits(limits);
+
+        let bets = vec![
+            RouletteBet::with_stake(RouletteBetType::Straight(17), Money::from_decimal("0.0025", Currency::Btc).unwrap()),
+            RouletteBet::announced(RouletteBetType::Split([0, 3]), 40, AnnouncedBet::JeuZero).in_currency(Currency::Btc),
+        ];
+        let (mut journal, _) = Journal::open(&dir.0).unwrap();
+        r.spin(&bets).unwrap();
+        journal.sync(r.spin_log()).unwrap();
+        drop(journal);
+
+        let (_, records) = Journal::open(&dir.0).unwrap();
+        assert_eq!(records[0].bets, bets);
+    }
+}
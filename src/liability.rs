@@ -0,0 +1,96 @@
+use crate::roulette::{PlaceBetError, RouletteBet, RouletteEvaluator, ZeroRule};
+use crate::wheel::WheelVariant;
+
+/// The gross payout the house would owe on each pocket of the wheel for a set of bets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiabilityTable {
+    payouts: Vec<u64>,
+}
+
+impl LiabilityTable {
+    /// Settles ```bets``` against every pocket of the wheel (37 or 38 entries, indexed by pocket number). Fails with
+    /// ```PayoutOverflow``` if the payout of a bet doesn't fit in a ```u64```; the payout of a pocket saturates.
+    pub fn new(variant: WheelVariant, zero_rule: ZeroRule, bets: &[RouletteBet]) -> Result<Self, PlaceBetError> {
+        let payouts = variant.pockets()
+            .map(|pocket| {
+                Ok(RouletteEvaluator::calculate_winnings(variant, zero_rule, pocket, bets)?
+                    .iter()
+                    .fold(0u64, |acc, result| acc.saturating_add(result.win())))
+            })
+            .collect::<Result<_, PlaceBetError>>()?;
+
+        Ok(Self {
+            payouts,
+        })
+    }
+
+    pub fn payout(&self, pocket: u8) -> u64 {
+        self.payouts[pocket as usize]
+    }
+
+    pub fn payouts(&self) -> &[u64] {
+        self.payouts.as_slice()
+    }
+
+    /// The pocket costing the house the most, and its payout. Ties go to the lowest pocket number.
+    pub fn worst(&self) -> (u8, u64) {
+        self.payouts.iter()
+            .enumerate()
+            .fold((0, 0), |worst, (pocket, &payout)| if payout > worst.1 { (pocket as u8, payout) } else { worst })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::outside::RedBlack;
+    use crate::roulette::{Roulette, RouletteBetType, PlaceBetError};
+    use crate::wheel::DOUBLE_ZERO;
+
+    #[test]
+    fn payouts_per_pocket() {
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Straight(17), 10),
+            RouletteBet::new(RouletteBetType::Split([17, 20]), 10),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Black), 100),
+        ];
+
+        let table = LiabilityTable::new(WheelVariant::European, ZeroRule::Standard, &bets).unwrap();
+        assert_eq!(table.payouts().len(), 37);
+        assert_eq!(table.payout(17), 360 + 180 + 200);
+        assert_eq!(table.payout(20), 180 + 200);
+        assert_eq!(table.payout(1), 0);
+        assert_eq!(table.payout(0), 0);
+        assert_eq!(table.worst(), (17, 740));
+
+        let table = LiabilityTable::new(WheelVariant::American, ZeroRule::Standard, &bets).unwrap();
+        assert_eq!(table.payouts().len(), 38);
+        assert_eq!(table.payout(DOUBLE_ZERO), 0);
+
+        let huge = RouletteBet::new(RouletteBetType::Straight(17), u64::MAX / 10);
+        assert_eq!(LiabilityTable::new(WheelVariant::European, ZeroRule::Standard, &[huge]), Err(PlaceBetError::PayoutOverflow(huge)));
+    }
+
+    #[test]
+    fn cap_rejects_bets() {
+        let mut r = Roulette::new();
+        r.set_liability_cap(Some(700));
+
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Straight(17), 10),
+            RouletteBet::new(RouletteBetType::Split([17, 20]), 10),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Black), 100),
+        ];
+
+        match r.spin(&bets) {
+            Err(errors) => match errors[..] {
+                [PlaceBetError::PocketLiabilityExceeded(pocket, liability, cap)] => assert_eq!((pocket, liability, cap), (17, 740, 700)),
+                _ => panic!("Unexpected errors: {:?}", errors),
+            },
+            Ok(_) => panic!("Bets over the liability cap were accepted"),
+        }
+
+        r.set_liability_cap(Some(740));
+        assert!(r.spin(&bets).is_ok());
+    }
+}
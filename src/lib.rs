@@ -0,0 +1,43 @@
+mod analysis;
+mod announced;
+mod board;
+mod fair;
+mod journal;
+mod liability;
+mod limits;
+mod money;
+mod notation;
+mod outside;
+mod record;
+mod rng;
+mod roulette;
+mod round;
+#[cfg(feature = "serde")]
+mod schema;
+mod settlement;
+mod simulation;
+mod strategy;
+mod table;
+mod wallet;
+mod wheel;
+
+pub use crate::analysis::*;
+pub use crate::announced::*;
+pub use crate::board::*;
+pub use crate::fair::*;
+pub use crate::journal::*;
+pub use crate::liability::*;
+pub use crate::limits::*;
+pub use crate::money::*;
+pub use crate::notation::*;
+pub use crate::outside::*;
+pub use crate::record::*;
+pub use crate::rng::*;
+pub use crate::roulette::*;
+pub use crate::round::*;
+pub use crate::settlement::*;
+pub use crate::simulation::*;
+pub use crate::strategy::*;
+pub use crate::table::*;
+pub use crate::wallet::*;
+pub use crate::wheel::*;
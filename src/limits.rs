@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use crate::money::{Currency, Money};
+use crate::roulette::{RouletteBet, PlaceBetError};
+
+/// The kinds of bet, without the numbers they cover. Table limits are configured per kind.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BetKind {
+    Straight,
+    Split,
+    Street,
+    Basket,
+    Topline,
+    Fiveline,
+    Corner,
+    Doubleline,
+    Dozens,
+    Columns,
+    EvenOdd,
+    Highlow,
+    Redblack,
+}
+
+impl BetKind {
+    /// Inside bets are placed on the numbers themselves, everything else is an outside bet
+    pub fn is_inside(&self) -> bool {
+        !matches!(self, BetKind::Dozens | BetKind::Columns | BetKind::EvenOdd | BetKind::Highlow | BetKind::Redblack)
+    }
+}
+
+/// Minimum and maximum wager allowed on a single bet
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BetLimit {
+    pub min: u64,
+    pub max: u64,
+}
+
+impl BetLimit {
+    pub fn new(min: u64, max: u64) -> Self {
+        Self {
+            min,
+            max,
+        }
+    }
+}
+
+impl Default for BetLimit {
+    fn default() -> Self {
+        Self::new(1, u64::MAX)
+    }
+}
+
+/// The limits of a table. A limit set for a specific ```BetKind``` takes precedence over the inside/outside limit
+/// of its category. The table max applies to the total of all the bets placed on a spin.
+/// Limits are in minor units of the table currency, and bets in any other currency are refused.
+#[derive(Debug, Clone, Default)]
+pub struct TableLimits {
+    inside: BetLimit,
+    outside: BetLimit,
+    per_kind: HashMap<BetKind, BetLimit>,
+    table_max: Option<u64>,
+    currency: Currency,
+}
+
+impl TableLimits {
+    pub fn new(inside: BetLimit, outside: BetLimit) -> Self {
+        Self {
+            inside,
+            outside,
+            per_kind: HashMap::new(),
+            table_max: None,
+            currency: Currency::Chips,
+        }
+    }
+
+    pub fn set_kind_limit(&mut self, kind: BetKind, limit: BetLimit) {
+        self.per_kind.insert(kind, limit);
+    }
+
+    pub fn set_table_max(&mut self, table_max: u64) {
+        self.table_max = Some(table_max);
+    }
+
+    pub fn table_max(&self) -> Option<u64> {
+        self.table_max
+    }
+
+    /// The currency bets are taken in, plain chips by default
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn set_currency(&mut self, currency: Currency) {
+        self.currency = currency;
+    }
+
+    /// The limit applied to a bet of the given kind, as amounts of the table currency
+    pub fn money_limit_for(&self, kind: BetKind) -> (Money, Money) {
+        let limit = self.limit_for(kind);
+        (Money::new(limit.min, self.currency), Money::new(limit.max, self.currency))
+    }
+
+    /// The limit applied to a bet of the given kind
+    pub fn limit_for(&self, kind: BetKind) -> BetLimit {
+        match self.per_kind.get(&kind) {
+            Some(limit) => *limit,
+            None if kind.is_inside() => self.inside,
+            None => self.outside,
+        }
+    }
+
+    /// Checks a single bet against its limit, returning the breached limit if any
+    pub fn check_bet(&self, bet: &RouletteBet) -> Option<PlaceBetError> {
+        let limit = self.limit_for(bet.bet_type().kind());
+        if bet.currency() != self.currency {
+            Some(PlaceBetError::CurrencyMismatch(*bet, self.currency))
+        } else if bet.wager() < limit.min {
+            Some(PlaceBetError::MinBetNotSatisfied(*bet, limit.min))
+        } else if bet.wager() > limit.max {
+            Some(PlaceBetError::MaxBetOnOption(*bet, limit.max))
+        } else {
+            None
+        }
+    }
+
+    /// Checks the total of all the bets against the table max
+    pub fn check_total(&self, bets: &[RouletteBet]) -> Option<PlaceBetError> {
+        let table_max = self.table_max?;
+        let total = bets.iter().fold(0u64, |acc, bet| acc.saturating_add(bet.wager()));
+
+        if total > table_max {
+            Some(PlaceBetError::TableMaxExceeded(total, table_max))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::outside::{Dozen, RedBlack};
+    use crate::roulette::{Roulette, RouletteBetType};
+
+    fn limits() -> TableLimits {
+        let mut limits = TableLimits::new(BetLimit::new(5, 100), BetLimit::new(10, 1000));
+        limits.set_kind_limit(BetKind::Straight, BetLimit::new(1, 50));
+        limits.set_table_max(1500);
+        limits
+    }
+
+    #[test]
+    fn limit_precedence() {
+        let limits = limits();
+        assert_eq!(limits.limit_for(BetKind::Straight), BetLimit::new(1, 50));
+        assert_eq!(limits.limit_for(BetKind::Corner), BetLimit::new(5, 100));
+        assert_eq!(limits.limit_for(BetKind::Redblack), BetLimit::new(10, 1000));
+    }
+
+    #[test]
+    fn violations_carry_the_limit() {
+        let mut r = Roulette::new();
+        r.set_limits(limits());
+
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Straight(17), 51),
+            RouletteBet::new(RouletteBetType::Split([16, 17]), 4),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 1000),
+            RouletteBet::new(RouletteBetType::Dozens(Dozen::Second), 9),
+            RouletteBet::new(RouletteBetType::Straight(40), 500),
+        ];
+
+        let errors = r.spin(&bets).err().unwrap();
+        let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(errors, vec![
+            "Max bet of 50 reached on option type: Straight(17), wager: 51",
+            "Minimum (5) not met for option type: Split(16, 17), wager: 4",
+            "Minimum (10) not met for option type: Dozens(2), wager: 9",
+            "Invalid Bet Option: type: Straight(40), wager: 500",
+            "Total of 1564 exceeds the table max of 1500",
+        ]);
+    }
+
+    #[test]
+    fn within_limits() {
+        let mut r = Roulette::new();
+        r.set_limits(limits());
+
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Straight(17), 50),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 1000),
+        ];
+        assert!(r.spin(&bets).is_ok());
+    }
+
+    #[test]
+    fn table_currency() {
+        let mut limits = limits();
+        limits.set_currency(Currency::Gbp);
+        assert_eq!(limits.money_limit_for(BetKind::Straight), (Money::new(1, Currency::Gbp), Money::new(50, Currency::Gbp)));
+
+        let mut r = Roulette::new();
+        r.set_limits(limits);
+        let pounds = RouletteBet::with_stake(RouletteBetType::Redblack(RedBlack::Red), Money::from_decimal("2.50", Currency::Gbp).unwrap());
+        let euros = pounds.in_currency(Currency::Eur);
+        let chips = RouletteBet::new(RouletteBetType::Straight(17), 10);
+        assert!(r.spin(&[pounds]).is_ok());
+
+        let errors: Vec<String> = r.spin(&[pounds, euros, chips]).err().unwrap().iter().map(|e| e.to_string()).collect();
+        assert_eq!(errors, vec![
+            "Option type: Redblack(red), wager: 2.50 EUR is not in the table currency (GBP)",
+            "Option type: Straight(17), wager: 10 is not in the table currency (GBP)",
+        ]);
+    }
+}
@@ -1,16 +1,11 @@
-mod roulette;
-
-use crate::roulette::*;
-
-fn calculate_total_bet(bets: &Vec<RouletteBet>) -> u64 {
-    bets.iter().fold(0, |acc, bet| acc + bet.wager())
-}
-
+use roulette::*;
 
 fn main() {
 
     // Simulate various bet types, starting from a balance 10k.
-    let mut balance = 10000;
+    let player = PlayerId(1);
+    let mut wallet = Ledger::new();
+    wallet.credit(player, 10000).unwrap();
     let mut r = Roulette::new();
     let bets = vec![
         RouletteBet::new(RouletteBetType::Straight(11), 100),
@@ -19,32 +14,42 @@ fn main() {
         RouletteBet::new(RouletteBetType::Corner([8, 9, 11, 12]), 100),
         RouletteBet::new(RouletteBetType::Corner([10, 11, 13, 14]), 100),
         RouletteBet::new(RouletteBetType::Corner([11, 12, 14, 15]), 100),
-        RouletteBet::new(RouletteBetType::Columns(2), 300),
+        RouletteBet::new(RouletteBetType::Columns(Column::Second), 300),
         RouletteBet::new(RouletteBetType::Basket([0, 1, 2]), 100),
         RouletteBet::new(RouletteBetType::Topline([0, 1, 2, 3]), 100),
-        RouletteBet::new(RouletteBetType::Dozens(1), 100), 
-        RouletteBet::new(RouletteBetType::EvenOdd(0), 100), 
-        RouletteBet::new(RouletteBetType::Highlow(1), 100),
-        RouletteBet::new(RouletteBetType::Redblack(1), 100),
+        RouletteBet::new(RouletteBetType::Dozens(Dozen::First), 100), 
+        RouletteBet::new(RouletteBetType::EvenOdd(Parity::Even), 100), 
+        RouletteBet::new(RouletteBetType::Highlow(Half::High), 100),
+        RouletteBet::new(RouletteBetType::Redblack(RedBlack::Black), 100),
         RouletteBet::new(RouletteBetType::Doubleline([25, 26, 27, 28, 29, 30]), 100),
     ];
 
+    // Swap in any other Strategy (Martingale, Fibonacci, ...) to play a progression instead
+    let mut strategy = FlatBet::new(bets);
+    let mut last: Option<RoundOutcome> = None;
+
 
     let mut counter = 1;
-    let mut highest_balance = balance;
+    let mut highest_balance = wallet.available(player);
 
     // Loop till we are out of balance or reached the highest balance.
     loop {
         // Verify enough funds, grab all the bets and spin. Add to the balance the winning results. 
 
         println!("\nGame {}", counter);
-        let total_bet = calculate_total_bet(&bets);
-        if total_bet > balance {
-            println!("Not enough balance to place the bet(s)! (balance: {}, bets: {})", balance, total_bet);
+        let bets = strategy.next_bets(last.as_ref(), wallet.available(player));
+        let total_bet = match total_wager(&bets, r.limits().currency()) {
+            Ok(total) => total.minor_units(),
+            Err(error) => {
+                println!("The bets add up to more than can be placed! ({})", error);
+                break;
+            }
+        };
+        if let Err(error) = wallet.reserve(player, total_bet) {
+            println!("Not enough balance to place the bet(s)! ({})", error);
             break;
         } else {
-            balance -= total_bet;
-            println!("Bets placed. Balance = {}", balance);
+            println!("Bets placed. Balance = {}", wallet.available(player));
         }
 
         
@@ -54,22 +59,28 @@ fn main() {
 
         match results {
             Ok(results) => {
-                println!("Ball dropped on {}", results.0);
+                println!("Ball dropped on {}", Pocket(results.0));
+                let mut returned = 0;
                 for (ndx, result) in results.1.iter().enumerate() {
                     println!("Bet {}: {} wins {}", ndx, result.bet(), result.win());
-                    balance += result.win();
+                    returned += result.win();
                 }
+                wallet.settle(player, total_bet, returned).unwrap();
+                last = Some(RoundOutcome { number: results.0, wagered: total_bet, returned });
             },
             Err(errors) => {
+                // the bets were not played, the stakes go back to the balance
+                wallet.release(player, total_bet).unwrap();
                 println!("Errors found:");
                 for error in errors {
                     println!("- {}", error);
                 }
+                break;
             }
         }
 
-        if balance > highest_balance {
-            highest_balance = balance;
+        if wallet.available(player) > highest_balance {
+            highest_balance = wallet.available(player);
         }
         counter += 1;
     }
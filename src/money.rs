@@ -0,0 +1,225 @@
+//! Amounts of money in minor units (cents, pence, satoshis) of a currency.
+//!
+//! Wagers are whole numbers of minor units: a bet of 12.50 EUR is a wager of 1250 cents. Tables that play with
+//! plain chips use ```Currency::Chips```, which has no decimals. Arithmetic on ```Money``` is checked: it fails on
+//! overflow and when the currencies differ, rather than wrapping or mixing them.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "UPPERCASE"))]
+pub enum Currency {
+    /// Plain chips, without a unit
+    #[default]
+    Chips,
+    Eur,
+    Gbp,
+    Usd,
+    Btc,
+}
+
+impl Currency {
+    /// Number of decimals of the currency, i.e. how many minor units make a unit
+    pub fn decimals(&self) -> u8 {
+        match self {
+            Currency::Chips => 0,
+            Currency::Eur | Currency::Gbp | Currency::Usd => 2,
+            Currency::Btc => 8,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Chips => "CHIPS",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Usd => "USD",
+            Currency::Btc => "BTC",
+        }
+    }
+
+    pub fn is_chips(&self) -> bool {
+        *self == Currency::Chips
+    }
+
+    /// Minor units in one unit of the currency
+    fn scale(&self) -> u64 {
+        10u64.pow(self.decimals() as u32)
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = MoneyError;
+
+    /// Parses a currency code, in any case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Currency::Chips, Currency::Eur, Currency::Gbp, Currency::Usd, Currency::Btc]
+            .iter()
+            .find(|currency| currency.code().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or(MoneyError::InvalidAmount)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    /// The amounts are in different currencies
+    CurrencyMismatch(Currency, Currency),
+
+    /// The result doesn't fit in a ```u64``` of minor units
+    Overflow,
+
+    /// Not a valid amount or currency, or an amount with more decimals than its currency has
+    InvalidAmount,
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoneyError::CurrencyMismatch(a, b) => write!(f, "Cannot mix {} and {}", a, b),
+            MoneyError::Overflow => write!(f, "Amount overflow"),
+            MoneyError::InvalidAmount => write!(f, "Invalid amount"),
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+/// An amount in minor units of a currency
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Money {
+    minor_units: u64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn new(minor_units: u64, currency: Currency) -> Self {
+        Self {
+            minor_units,
+            currency,
+        }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    pub fn chips(chips: u64) -> Self {
+        Self::new(chips, Currency::Chips)
+    }
+
+    /// Parses a decimal amount such as ```12.5``` in the given currency
+    pub fn from_decimal(amount: &str, currency: Currency) -> Result<Self, MoneyError> {
+        let (units, fraction) = match amount.find('.') {
+            Some(dot) => (&amount[..dot], &amount[dot + 1..]),
+            None => (amount, ""),
+        };
+        let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if units.is_empty() || !is_digits(units) || !is_digits(fraction) || fraction.len() > currency.decimals() as usize {
+            return Err(MoneyError::InvalidAmount);
+        }
+
+        let units: u64 = units.parse().map_err(|_| MoneyError::Overflow)?;
+        let fraction: u64 = format!("{:0<width$}", fraction, width = currency.decimals() as usize).parse().unwrap_or(0);
+        let minor_units = units
+            .checked_mul(currency.scale())
+            .and_then(|minor| minor.checked_add(fraction))
+            .ok_or(MoneyError::Overflow)?;
+        Ok(Self::new(minor_units, currency))
+    }
+
+    pub fn minor_units(&self) -> u64 {
+        self.minor_units
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.minor_units == 0
+    }
+
+    pub fn checked_add(self, other: Money) -> Result<Money, MoneyError> {
+        self.same_currency(other)?;
+        let sum = self.minor_units.checked_add(other.minor_units).ok_or(MoneyError::Overflow)?;
+        Ok(Self::new(sum, self.currency))
+    }
+
+    /// Fails with ```Overflow``` if ```other``` is larger
+    pub fn checked_sub(self, other: Money) -> Result<Money, MoneyError> {
+        self.same_currency(other)?;
+        let difference = self.minor_units.checked_sub(other.minor_units).ok_or(MoneyError::Overflow)?;
+        Ok(Self::new(difference, self.currency))
+    }
+
+    pub fn checked_mul(self, factor: u64) -> Result<Money, MoneyError> {
+        let product = self.minor_units.checked_mul(factor).ok_or(MoneyError::Overflow)?;
+        Ok(Self::new(product, self.currency))
+    }
+
+    /// The total of the amounts, which must all be in ```currency```
+    pub fn checked_sum<I: IntoIterator<Item = Money>>(amounts: I, currency: Currency) -> Result<Money, MoneyError> {
+        amounts.into_iter().try_fold(Money::zero(currency), |total, amount| total.checked_add(amount))
+    }
+
+    fn same_currency(&self, other: Money) -> Result<(), MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch(self.currency, other.currency));
+        }
+        Ok(())
+    }
+}
+
+/// Chips are written as a plain number, other currencies with their decimals and code: ```12.50 EUR```
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.currency.is_chips() {
+            return write!(f, "{}", self.minor_units);
+        }
+        let scale = self.currency.scale();
+        write!(f, "{}.{:0width$} {}", self.minor_units / scale, self.minor_units % scale, self.currency,
+            width = self.currency.decimals() as usize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decimals() {
+        assert_eq!(Money::from_decimal("12.5", Currency::Eur), Ok(Money::new(1250, Currency::Eur)));
+        assert_eq!(Money::from_decimal("0.00000001", Currency::Btc), Ok(Money::new(1, Currency::Btc)));
+        assert_eq!(Money::from_decimal("7", Currency::Gbp), Ok(Money::new(700, Currency::Gbp)));
+        assert_eq!(Money::from_decimal("1.005", Currency::Usd), Err(MoneyError::InvalidAmount));
+        assert_eq!(Money::from_decimal("1.5", Currency::Chips), Err(MoneyError::InvalidAmount));
+        assert_eq!(Money::from_decimal("-1", Currency::Eur), Err(MoneyError::InvalidAmount));
+        assert_eq!(Money::from_decimal("184467440737095516.16", Currency::Eur), Err(MoneyError::Overflow));
+
+        assert_eq!(Money::new(1250, Currency::Eur).to_string(), "12.50 EUR");
+        assert_eq!(Money::new(5, Currency::Btc).to_string(), "0.00000005 BTC");
+        assert_eq!(Money::chips(25).to_string(), "25");
+        assert_eq!("gbp".parse(), Ok(Currency::Gbp));
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let eur = |minor| Money::new(minor, Currency::Eur);
+        assert_eq!(eur(150).checked_add(eur(50)), Ok(eur(200)));
+        assert_eq!(eur(150).checked_add(Money::new(50, Currency::Gbp)), Err(MoneyError::CurrencyMismatch(Currency::Eur, Currency::Gbp)));
+        assert_eq!(eur(u64::MAX).checked_add(eur(1)), Err(MoneyError::Overflow));
+        assert_eq!(eur(50).checked_sub(eur(150)), Err(MoneyError::Overflow));
+        assert_eq!(eur(u64::MAX / 2).checked_mul(3), Err(MoneyError::Overflow));
+        assert_eq!(Money::checked_sum(vec![eur(1), eur(2)], Currency::Eur), Ok(eur(3)));
+        assert_eq!(Money::checked_sum(vec![eur(1)], Currency::Usd), Err(MoneyError::CurrencyMismatch(Currency::Usd, Currency::Eur)));
+    }
+}
@@ -0,0 +1,543 @@
+//! Text notation for bets.
+//!
+//! Bets can be typed in a short notation, ```<bet type> x <wager>```:
+//!
+//! ```text
+//! straight 17 x 25        split 10-11 x 5         corner 8/9/11/12 x 10
+//! red x 100               dozen 2 x 50            voisins x 9
+//! ```
+//!
+//! The forms written by the ```Display``` impls are accepted as well, so a formatted bet parses back to the same
+//! bet: ```type: Split(10, 11), wager: 5``` or ```type: Straight(17), wager: 5 (17 and 2 neighbours)```.
+//! Keywords are case insensitive and double zero is written ```00```. Wagers are in chips unless followed by a
+//! currency: ```red x 12.50 EUR```.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use crate::announced::AnnouncedBet;
+use crate::money::{Currency, Money};
+use crate::outside::{Half, Parity, RedBlack};
+use crate::roulette::{RouletteBet, RouletteBetType, PlaceBetError};
+use crate::wheel::{WheelVariant, DOUBLE_ZERO};
+
+/// Why a bet could not be parsed: the offending token, its byte offset in the input and what was expected there.
+/// At the end of the input the token is empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBetError {
+    token: String,
+    position: usize,
+    expected: &'static str,
+}
+
+impl ParseBetError {
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+}
+
+impl fmt::Display for ParseBetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.token.is_empty() {
+            write!(f, "Unexpected end of input at position {}, expected {}", self.position, self.expected)
+        } else {
+            write!(f, "Unexpected '{}' at position {}, expected {}", self.token, self.position, self.expected)
+        }
+    }
+}
+
+impl std::error::Error for ParseBetError {}
+
+/// A parsed line of notation: either a single bet, or an announced bet with its chip value
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParsedBet {
+    Bet(RouletteBet),
+    Announced(AnnouncedBet, u64),
+}
+
+impl ParsedBet {
+    /// The bets to place on the given wheel
+    pub fn bets(&self, variant: WheelVariant) -> Result<Vec<RouletteBet>, PlaceBetError> {
+        match self {
+            ParsedBet::Bet(bet) => Ok(vec![*bet]),
+            ParsedBet::Announced(announcement, chip) => announcement.bets(variant, *chip),
+        }
+    }
+}
+
+impl FromStr for ParsedBet {
+    type Err = ParseBetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let parsed = if parser.at_announcement() {
+            let announcement = parser.announcement()?;
+            parser.expect("x", "'x' and the chip value")?;
+            ParsedBet::Announced(announcement, parser.wager()?)
+        } else {
+            ParsedBet::Bet(parser.bet()?)
+        };
+        parser.end()?;
+        Ok(parsed)
+    }
+}
+
+impl FromStr for RouletteBet {
+    type Err = ParseBetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let bet = parser.bet()?;
+        parser.end()?;
+        Ok(bet)
+    }
+}
+
+impl FromStr for RouletteBetType {
+    type Err = ParseBetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let bet_type = parser.bet_type()?;
+        parser.end()?;
+        Ok(bet_type)
+    }
+}
+
+impl FromStr for AnnouncedBet {
+    type Err = ParseBetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let announcement = parser.announcement()?;
+        parser.end()?;
+        Ok(announcement)
+    }
+}
+
+const ANNOUNCEMENTS: [&str; 7] = ["voisins", "tiers", "orphelins", "jeu", "neighbours", "neighbors", "finale"];
+
+struct Parser {
+    /// Lower cased tokens, with their text and byte offset in the input
+    tokens: Vec<(String, String, usize)>,
+    next: usize,
+    len: usize,
+}
+
+impl Parser {
+    /// Splits the input into words (runs of letters and digits) and single punctuation characters
+    fn new(s: &str) -> Self {
+        let mut tokens: Vec<(String, String, usize)> = Vec::new();
+        let mut in_word = false;
+
+        for (offset, c) in s.char_indices() {
+            if c.is_alphanumeric() {
+                match tokens.last_mut() {
+                    Some((word, text, _)) if in_word => {
+                        word.extend(c.to_lowercase());
+                        text.push(c);
+                    },
+                    _ => tokens.push((c.to_lowercase().collect(), c.to_string(), offset)),
+                }
+                in_word = true;
+            } else {
+                if !c.is_whitespace() {
+                    tokens.push((c.to_string(), c.to_string(), offset));
+                }
+                in_word = false;
+            }
+        }
+
+        Self {
+            tokens,
+            next: 0,
+            len: s.len(),
+        }
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<&str> {
+        self.tokens.get(self.next + ahead).map(|(token, _, _)| token.as_str())
+    }
+
+    /// An error about the next token
+    fn error(&self, expected: &'static str) -> ParseBetError {
+        match self.tokens.get(self.next) {
+            Some((_, text, position)) => ParseBetError { token: text.clone(), position: *position, expected },
+            None => ParseBetError { token: String::new(), position: self.len, expected },
+        }
+    }
+
+    fn accept(&mut self, token: &str) -> bool {
+        if self.peek() == Some(token) {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str, expected: &'static str) -> Result<(), ParseBetError> {
+        if self.accept(token) {
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn end(&self) -> Result<(), ParseBetError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error("end of input")),
+        }
+    }
+
+    fn word(&mut self, expected: &'static str) -> Result<String, ParseBetError> {
+        match self.tokens.get(self.next) {
+            Some((token, _, _)) if token.chars().all(char::is_alphanumeric) => {
+                self.next += 1;
+                Ok(token.clone())
+            },
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    /// A pocket number, ```00``` being double zero
+    fn number(&mut self) -> Result<u8, ParseBetError> {
+        let number = match self.peek() {
+            Some("00") => Some(DOUBLE_ZERO),
+            Some(token) => token.parse::<u8>().ok(),
+            None => None,
+        };
+        match number {
+            Some(number) => {
+                self.next += 1;
+                Ok(number)
+            },
+            None => Err(self.error("a number")),
+        }
+    }
+
+    /// A wager in chips, or an amount followed by its currency: ```12.50 EUR```
+    fn stake(&mut self) -> Result<Money, ParseBetError> {
+        let is_number = |token: Option<&str>| token.is_some_and(|t| t.bytes().all(|b| b.is_ascii_digit()));
+        if !is_number(self.peek()) {
+            return Err(self.error("a wager"));
+        }
+
+        let start = self.next;
+        let mut amount = self.peek().unwrap_or_default().to_string();
+        self.next += 1;
+        if self.peek() == Some(".") && is_number(self.peek_at(1)) {
+            amount.push('.');
+            amount.push_str(self.peek_at(1).unwrap_or_default());
+            self.next += 2;
+        }
+        let currency = match self.peek().and_then(|token| token.parse::<Currency>().ok()) {
+            Some(currency) => {
+                self.next += 1;
+                currency
+            },
+            None => Currency::Chips,
+        };
+
+        Money::from_decimal(&amount, currency).map_err(|_| {
+            self.next = start;
+            self.error("a wager")
+        })
+    }
+
+    fn wager(&mut self) -> Result<u64, ParseBetError> {
+        match self.peek().and_then(|token| token.parse::<u64>().ok()) {
+            Some(wager) => {
+                self.next += 1;
+                Ok(wager)
+            },
+            None => Err(self.error("a wager")),
+        }
+    }
+
+    /// Either ```type: <bet type>, wager: <n> [(announcement)]``` or ```<bet type> x <n>```
+    fn bet(&mut self) -> Result<RouletteBet, ParseBetError> {
+        if self.accept("type") {
+            self.expect(":", "':'")?;
+            let bet_type = self.bet_type()?;
+            self.expect(",", "','")?;
+            self.expect("wager", "'wager'")?;
+            self.expect(":", "':'")?;
+            let stake = self.stake()?;
+
+            if self.accept("(") {
+                let announcement = self.announcement()?;
+                self.expect(")", "')'")?;
+                Ok(RouletteBet::announced(bet_type, stake.minor_units(), announcement).in_currency(stake.currency()))
+            } else {
+                Ok(RouletteBet::with_stake(bet_type, stake))
+            }
+        } else {
+            let bet_type = self.bet_type()?;
+            self.expect("x", "'x' and the wager")?;
+            Ok(RouletteBet::with_stake(bet_type, self.stake()?))
+        }
+    }
+
+    fn bet_type(&mut self) -> Result<RouletteBetType, ParseBetError> {
+        let start = self.next;
+        let kind = self.word("a bet type")?;
+
+        // the even money bets can be named on their own
+        match kind.as_str() {
+            "red" => return Ok(RouletteBetType::Redblack(RedBlack::Red)),
+            "black" => return Ok(RouletteBetType::Redblack(RedBlack::Black)),
+            "even" => return Ok(RouletteBetType::EvenOdd(Parity::Even)),
+            "odd" => return Ok(RouletteBetType::EvenOdd(Parity::Odd)),
+            "low" => return Ok(RouletteBetType::Highlow(Half::Low)),
+            "high" => return Ok(RouletteBetType::Highlow(Half::High)),
+            _ => (),
+        }
+
+        // arguments are either in brackets, as displayed, or follow the bet type directly
+        let bracketed = self.accept("(");
+        let bet_type = match kind.as_str() {
+            "straight" | "number" => RouletteBetType::Straight(self.number()?),
+            "split" => RouletteBetType::Split(self.numbers(bracketed)?),
+            "street" => RouletteBetType::Street(self.numbers(bracketed)?),
+            "basket" | "trio" => RouletteBetType::Basket(self.numbers(bracketed)?),
+            "topline" => RouletteBetType::Topline(self.numbers(bracketed)?),
+            "fiveline" => RouletteBetType::Fiveline(self.numbers(bracketed)?),
+            "corner" => RouletteBetType::Corner(self.numbers(bracketed)?),
+            "doubleline" | "sixline" => RouletteBetType::Doubleline(self.numbers(bracketed)?),
+            "dozen" | "dozens" => RouletteBetType::Dozens(self.selector("a dozen (1, 2 or 3)")?),
+            "column" | "columns" => RouletteBetType::Columns(self.selector("a column (1, 2 or 3)")?),
+            "evenodd" => RouletteBetType::EvenOdd(self.choice(("even", Parity::Even), ("odd", Parity::Odd))?),
+            "redblack" => RouletteBetType::Redblack(self.choice(("red", RedBlack::Red), ("black", RedBlack::Black))?),
+            "highlow" => RouletteBetType::Highlow(self.high_low()?),
+            _ => {
+                self.next = start;
+                return Err(self.error("a bet type"));
+            },
+        };
+
+        if bracketed {
+            self.expect(")", "')'")?;
+        }
+        Ok(bet_type)
+    }
+
+    /// ```N``` numbers separated by commas when bracketed, or by '-', '/' or ',' otherwise
+    fn numbers<const N: usize>(&mut self, bracketed: bool) -> Result<[u8; N], ParseBetError> {
+        let mut numbers = [0u8; N];
+        for (i, number) in numbers.iter_mut().enumerate() {
+            if i > 0 {
+                let separated = match self.peek() {
+                    Some(",") => true,
+                    Some("-") | Some("/") => !bracketed,
+                    _ => false,
+                };
+                if !separated {
+                    return Err(self.error(if bracketed { "',' and another number" } else { "'-' and another number" }));
+                }
+                self.next += 1;
+            }
+            *number = self.number()?;
+        }
+        Ok(numbers)
+    }
+
+    /// The side of an even money bet, by name or as 0/1
+    fn choice<T>(&mut self, zero: (&str, T), one: (&str, T)) -> Result<T, ParseBetError> {
+        if self.accept(zero.0) || self.accept("0") {
+            Ok(zero.1)
+        } else if self.accept(one.0) || self.accept("1") {
+            Ok(one.1)
+        } else {
+            Err(self.error("a side of the bet"))
+        }
+    }
+
+    /// ```low```/```high```, ```1-18```/```19-36``` or 0/1
+    fn high_low(&mut self) -> Result<Half, ParseBetError> {
+        match (self.peek(), self.peek_at(1), self.peek_at(2)) {
+            (Some("1"), Some("-"), Some("18")) => {
+                self.next += 3;
+                Ok(Half::Low)
+            },
+            (Some("19"), Some("-"), Some("36")) => {
+                self.next += 3;
+                Ok(Half::High)
+            },
+            _ => self.choice(("low", Half::Low), ("high", Half::High)),
+        }
+    }
+
+    /// A dozen or column, by its number
+    fn selector<T: TryFrom<u8>>(&mut self, expected: &'static str) -> Result<T, ParseBetError> {
+        match self.peek().and_then(|token| token.parse::<u8>().ok()).and_then(|code| T::try_from(code).ok()) {
+            Some(selector) => {
+                self.next += 1;
+                Ok(selector)
+            },
+            None => Err(self.error(expected)),
+        }
+    }
+
+    fn at_announcement(&self) -> bool {
+        match self.peek() {
+            Some(token) if ANNOUNCEMENTS.contains(&token) => true,
+            // "17 and 2 neighbours"
+            Some(_) => self.peek_at(1) == Some("and"),
+            None => false,
+        }
+    }
+
+    fn announcement(&mut self) -> Result<AnnouncedBet, ParseBetError> {
+        if self.peek_at(1) == Some("and") {
+            let number = self.number()?;
+            self.next += 1;
+            let count = self.number()?;
+            if !(self.accept("neighbours") || self.accept("neighbors")) {
+                return Err(self.error("'neighbours'"));
+            }
+            return Ok(AnnouncedBet::Neighbours { number, count });
+        }
+
+        let start = self.next;
+        let announcement = match self.word("an announced bet")?.as_str() {
+            "voisins" => {
+                if self.accept("du") && !(self.accept("zéro") || self.accept("zero")) {
+                    return Err(self.error("'zéro'"));
+                }
+                AnnouncedBet::VoisinsDuZero
+            },
+            "tiers" => {
+                if self.accept("du") {
+                    self.expect("cylindre", "'cylindre'")?;
+                }
+                AnnouncedBet::Tiers
+            },
+            "orphelins" => AnnouncedBet::Orphelins,
+            "jeu" => {
+                if !(self.accept("zéro") || self.accept("zero")) {
+                    return Err(self.error("'zéro'"));
+                }
+                AnnouncedBet::JeuZero
+            },
+            "neighbours" | "neighbors" => {
+                let number = self.number()?;
+                let count = self.number()?;
+                AnnouncedBet::Neighbours { number, count }
+            },
+            "finale" => {
+                let first = self.number()?;
+                if self.accept("/") {
+                    AnnouncedBet::FinaleACheval(first, self.number()?)
+                } else {
+                    AnnouncedBet::FinaleEnPlein(first)
+                }
+            },
+            _ => {
+                self.next = start;
+                return Err(self.error("an announced bet"));
+            },
+        };
+        Ok(announcement)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::outside::{Column, Dozen};
+
+    #[test]
+    fn notation() {
+        let bet = |bet_type, wager| ParsedBet::Bet(RouletteBet::new(bet_type, wager));
+
+        assert_eq!("straight 17 x 25".parse(), Ok(bet(RouletteBetType::Straight(17), 25)));
+        assert_eq!("split 10-11 x 5".parse(), Ok(bet(RouletteBetType::Split([10, 11]), 5)));
+        assert_eq!("corner 8/9/11/12 x 10".parse(), Ok(bet(RouletteBetType::Corner([8, 9, 11, 12]), 10)));
+        assert_eq!("red x 100".parse(), Ok(bet(RouletteBetType::Redblack(RedBlack::Red), 100)));
+        assert_eq!("Dozen 2 X 50".parse(), Ok(bet(RouletteBetType::Dozens(Dozen::Second), 50)));
+        assert_eq!("basket 0-2-00 x 5".parse(), Ok(bet(RouletteBetType::Basket([0, 2, DOUBLE_ZERO]), 5)));
+        assert_eq!("voisins x 9".parse(), Ok(ParsedBet::Announced(AnnouncedBet::VoisinsDuZero, 9)));
+        assert_eq!("finale 3/6 x 2".parse(), Ok(ParsedBet::Announced(AnnouncedBet::FinaleACheval(3, 6), 2)));
+        assert_eq!("17 and 2 neighbours x 5".parse(), Ok(ParsedBet::Announced(AnnouncedBet::Neighbours { number: 17, count: 2 }, 5)));
+
+        let euros = |bet_type, minor| ParsedBet::Bet(RouletteBet::new(bet_type, minor).in_currency(Currency::Eur));
+        assert_eq!("red x 12.5 EUR".parse(), Ok(euros(RouletteBetType::Redblack(RedBlack::Red), 1250)));
+        assert_eq!("straight 17 x 2 eur".parse(), Ok(euros(RouletteBetType::Straight(17), 200)));
+
+        let voisins: ParsedBet = "voisins x 9".parse().unwrap();
+        assert_eq!(voisins.bets(WheelVariant::European).unwrap().len(), 7);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Straight(DOUBLE_ZERO), 1),
+            RouletteBet::new(RouletteBetType::Split([0, DOUBLE_ZERO]), 2),
+            RouletteBet::new(RouletteBetType::Street([13, 14, 15]), 3),
+            RouletteBet::new(RouletteBetType::Basket([2, 3, DOUBLE_ZERO]), 4),
+            RouletteBet::new(RouletteBetType::Topline([0, 1, 2, 3]), 5),
+            RouletteBet::new(RouletteBetType::Fiveline([0, 1, 2, 3, DOUBLE_ZERO]), 6),
+            RouletteBet::new(RouletteBetType::Corner([1, 2, 4, 5]), 7),
+            RouletteBet::new(RouletteBetType::Doubleline([1, 2, 3, 4, 5, 6]), 8),
+            RouletteBet::new(RouletteBetType::Dozens(Dozen::Third), 9),
+            RouletteBet::new(RouletteBetType::Columns(Column::First), 10),
+            RouletteBet::new(RouletteBetType::EvenOdd(Parity::Odd), 11),
+            RouletteBet::new(RouletteBetType::Highlow(Half::Low), 12),
+            RouletteBet::new(RouletteBetType::Highlow(Half::High), 13),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Black), 14),
+            RouletteBet::announced(RouletteBetType::Split([0, 3]), 20, AnnouncedBet::JeuZero),
+            RouletteBet::announced(RouletteBetType::Straight(17), 5, AnnouncedBet::Neighbours { number: 17, count: 2 }),
+            RouletteBet::announced(RouletteBetType::Straight(7), 5, AnnouncedBet::FinaleEnPlein(7)),
+            RouletteBet::new(RouletteBetType::Straight(7), 1250).in_currency(Currency::Eur),
+            RouletteBet::announced(RouletteBetType::Straight(26), 5, AnnouncedBet::JeuZero).in_currency(Currency::Btc),
+        ];
+
+        for bet in bets {
+            assert_eq!(bet.to_string().parse::<RouletteBet>(), Ok(bet), "{}", bet);
+            assert_eq!(bet.bet_type().to_string().parse::<RouletteBetType>(), Ok(bet.bet_type()));
+        }
+
+        for announcement in &[AnnouncedBet::VoisinsDuZero, AnnouncedBet::Tiers, AnnouncedBet::Orphelins, AnnouncedBet::FinaleACheval(3, 6)] {
+            assert_eq!(announcement.to_string().parse::<AnnouncedBet>(), Ok(*announcement));
+        }
+    }
+
+    #[test]
+    fn errors_point_at_the_token() {
+        let error = "split 10-x x 5".parse::<RouletteBet>().unwrap_err();
+        assert_eq!((error.token(), error.position()), ("x", 9));
+        assert_eq!(error.to_string(), "Unexpected 'x' at position 9, expected a number");
+
+        let error = "Square 1/2/4/5 x 5".parse::<RouletteBet>().unwrap_err();
+        assert_eq!((error.token(), error.position()), ("Square", 0));
+
+        let error = "straight 17 x".parse::<RouletteBet>().unwrap_err();
+        assert_eq!(error.to_string(), "Unexpected end of input at position 13, expected a wager");
+
+        let error = "straight 17 x 5 red".parse::<ParsedBet>().unwrap_err();
+        assert_eq!((error.token(), error.position(), error.expected()), ("red", 16, "end of input"));
+
+        let error = "corner 8/9/11 x 10".parse::<RouletteBet>().unwrap_err();
+        assert_eq!((error.token(), error.position()), ("x", 14));
+
+        let error = "red x 1.005 usd".parse::<RouletteBet>().unwrap_err();
+        assert_eq!((error.token(), error.position(), error.expected()), ("1", 6, "a wager"));
+
+        let error = "dozen 4 x 10".parse::<RouletteBet>().unwrap_err();
+        assert_eq!((error.token(), error.position(), error.expected()), ("4", 6, "a dozen (1, 2 or 3)"));
+    }
+}
@@ -0,0 +1,338 @@
+use std::convert::TryFrom;
+use std::fmt;
+use crate::wheel::WheelVariant;
+
+/// A numeric code that doesn't match any selector of an outside bet (e.g. a dozen of 4)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InvalidSelector(pub u8);
+
+impl fmt::Display for InvalidSelector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid outside bet selector: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidSelector {}
+
+/// Even or odd. Zero is neither.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+/// 1-18 (low) or 19-36 (high)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Half {
+    Low,
+    High,
+}
+
+/// The colour of a pocket. The zero pockets are green, see ```RedBlack``` for the colours that can be bet on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum Colour {
+    Red,
+    Black,
+    Green,
+}
+
+/// The colours of a red or black bet
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RedBlack {
+    Red,
+    Black,
+}
+
+/// 1-12, 13-24 or 25-36
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Dozen {
+    First,
+    Second,
+    Third,
+}
+
+/// The columns of the layout, named after the number at their top: 1 (1, 4, 7...), 2 (2, 5, 8...) and 3 (3, 6, 9...)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Column {
+    First,
+    Second,
+    Third,
+}
+
+impl Parity {
+    pub fn of(number: u8) -> Option<Self> {
+        match (number, number % 2) {
+            (1..=36, 0) => Some(Parity::Even),
+            (1..=36, _) => Some(Parity::Odd),
+            _ => None,
+        }
+    }
+}
+
+impl Half {
+    pub fn of(number: u8) -> Option<Self> {
+        match number {
+            1..=18 => Some(Half::Low),
+            19..=36 => Some(Half::High),
+            _ => None,
+        }
+    }
+}
+
+impl Colour {
+    pub fn of(variant: WheelVariant, number: u8) -> Self {
+        match number {
+            n if variant.is_zero(n) => Colour::Green,
+            1 | 3 | 5 | 7 | 9 | 12 | 14 | 16 | 18 | 19 | 21 | 23 | 25 | 27 | 30 | 32 | 34 | 36 => Colour::Red,
+            _ => Colour::Black,
+        }
+    }
+}
+
+impl RedBlack {
+    /// ```None``` for the zero pockets
+    pub fn of(variant: WheelVariant, number: u8) -> Option<Self> {
+        match Colour::of(variant, number) {
+            Colour::Red => Some(RedBlack::Red),
+            Colour::Black => Some(RedBlack::Black),
+            Colour::Green => None,
+        }
+    }
+}
+
+impl From<RedBlack> for Colour {
+    fn from(colour: RedBlack) -> Self {
+        match colour {
+            RedBlack::Red => Colour::Red,
+            RedBlack::Black => Colour::Black,
+        }
+    }
+}
+
+impl Dozen {
+    pub fn of(number: u8) -> Option<Self> {
+        match number {
+            1..=12 => Some(Dozen::First),
+            13..=24 => Some(Dozen::Second),
+            25..=36 => Some(Dozen::Third),
+            _ => None,
+        }
+    }
+}
+
+impl Column {
+    pub fn of(number: u8) -> Option<Self> {
+        match number {
+            1..=36 => Column::try_from((number - 1) % 3 + 1).ok(),
+            _ => None,
+        }
+    }
+}
+
+// The numeric codes of the outside bets, as they were before they got their own types:
+// even 0 / odd 1, low 0 / high 1, red 0 / black 1, dozens and columns 1 to 3.
+
+impl From<Parity> for u8 {
+    fn from(parity: Parity) -> Self {
+        match parity {
+            Parity::Even => 0,
+            Parity::Odd => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for Parity {
+    type Error = InvalidSelector;
+
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        match code {
+            0 => Ok(Parity::Even),
+            1 => Ok(Parity::Odd),
+            _ => Err(InvalidSelector(code)),
+        }
+    }
+}
+
+impl From<Half> for u8 {
+    fn from(half: Half) -> Self {
+        match half {
+            Half::Low => 0,
+            Half::High => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for Half {
+    type Error = InvalidSelector;
+
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        match code {
+            0 => Ok(Half::Low),
+            1 => Ok(Half::High),
+            _ => Err(InvalidSelector(code)),
+        }
+    }
+}
+
+impl From<RedBlack> for u8 {
+    fn from(colour: RedBlack) -> Self {
+        match colour {
+            RedBlack::Red => 0,
+            RedBlack::Black => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for RedBlack {
+    type Error = InvalidSelector;
+
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        match code {
+            0 => Ok(RedBlack::Red),
+            1 => Ok(RedBlack::Black),
+            _ => Err(InvalidSelector(code)),
+        }
+    }
+}
+
+impl From<Dozen> for u8 {
+    fn from(dozen: Dozen) -> Self {
+        match dozen {
+            Dozen::First => 1,
+            Dozen::Second => 2,
+            Dozen::Third => 3,
+        }
+    }
+}
+
+impl TryFrom<u8> for Dozen {
+    type Error = InvalidSelector;
+
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        match code {
+            1 => Ok(Dozen::First),
+            2 => Ok(Dozen::Second),
+            3 => Ok(Dozen::Third),
+            _ => Err(InvalidSelector(code)),
+        }
+    }
+}
+
+impl From<Column> for u8 {
+    fn from(column: Column) -> Self {
+        match column {
+            Column::First => 1,
+            Column::Second => 2,
+            Column::Third => 3,
+        }
+    }
+}
+
+impl TryFrom<u8> for Column {
+    type Error = InvalidSelector;
+
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        match code {
+            1 => Ok(Column::First),
+            2 => Ok(Column::Second),
+            3 => Ok(Column::Third),
+            _ => Err(InvalidSelector(code)),
+        }
+    }
+}
+
+impl fmt::Display for Parity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match self {
+            Parity::Even => "even",
+            Parity::Odd => "odd",
+        })
+    }
+}
+
+impl fmt::Display for Half {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match self {
+            Half::Low => "1-18",
+            Half::High => "19-36",
+        })
+    }
+}
+
+impl fmt::Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match self {
+            Colour::Red => "red",
+            Colour::Black => "black",
+            Colour::Green => "green",
+        })
+    }
+}
+
+impl fmt::Display for RedBlack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Colour::from(*self))
+    }
+}
+
+impl fmt::Display for Dozen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", u8::from(*self))
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", u8::from(*self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::wheel::DOUBLE_ZERO;
+
+    #[test]
+    fn numeric_codes_round_trip() {
+        for code in 0..=255u8 {
+            if let Ok(parity) = Parity::try_from(code) {
+                assert_eq!(u8::from(parity), code);
+            }
+            if let Ok(half) = Half::try_from(code) {
+                assert_eq!(u8::from(half), code);
+            }
+            if let Ok(colour) = RedBlack::try_from(code) {
+                assert_eq!(u8::from(colour), code);
+            }
+            if let Ok(dozen) = Dozen::try_from(code) {
+                assert_eq!(u8::from(dozen), code);
+            }
+            if let Ok(column) = Column::try_from(code) {
+                assert_eq!(u8::from(column), code);
+            }
+        }
+
+        assert_eq!(Dozen::try_from(4), Err(InvalidSelector(4)));
+        assert_eq!(Column::try_from(0), Err(InvalidSelector(0)));
+        assert_eq!(Parity::try_from(2), Err(InvalidSelector(2)));
+        assert_eq!(RedBlack::try_from(2), Err(InvalidSelector(2)));
+    }
+
+    #[test]
+    fn pockets() {
+        assert_eq!(Parity::of(0), None);
+        assert_eq!(Parity::of(36), Some(Parity::Even));
+        assert_eq!(Half::of(DOUBLE_ZERO), None);
+        assert_eq!(Half::of(19), Some(Half::High));
+        assert_eq!(Dozen::of(13), Some(Dozen::Second));
+        assert_eq!(Column::of(34), Some(Column::First));
+        assert_eq!(Column::of(36), Some(Column::Third));
+        assert_eq!(Column::of(DOUBLE_ZERO), None);
+        assert_eq!(Colour::of(WheelVariant::American, DOUBLE_ZERO), Colour::Green);
+        assert_eq!(Colour::of(WheelVariant::European, 32), Colour::Red);
+        assert_eq!(Colour::of(WheelVariant::European, 15), Colour::Black);
+        assert_eq!(RedBlack::of(WheelVariant::European, 0), None);
+        assert_eq!(RedBlack::of(WheelVariant::European, 32), Some(RedBlack::Red));
+    }
+}
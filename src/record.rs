@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+use std::time::SystemTime;
+use crate::roulette::{merge_bets, RouletteBet};
+use crate::wheel::WheelVariant;
+
+/// Everything an auditor needs to know about a single spin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpinRecord {
+    /// Sequential id, starting at 1 for the first spin of a table
+    pub id: u64,
+    pub timestamp: SystemTime,
+    pub variant: WheelVariant,
+
+    /// Where the winning pocket came from, as reported by ```SpinRng::provenance``` just before the draw
+    pub rng: String,
+
+    pub number: u8,
+
+    /// The bets placed on this spin, in the order they were placed: the ```index``` of a ```BetId``` points into them.
+    /// Bets released from prison are only counted in ```total_paid```.
+    pub bets: Vec<RouletteBet>,
+    pub total_wagered: u64,
+    pub total_paid: u64,
+
+    /// What the house made on the spin: wagered minus paid
+    pub house_net: i64,
+}
+
+impl SpinRecord {
+    /// The bets of the spin merged per spot, see ```merge_bets```
+    pub fn merged_bets(&self) -> Vec<RouletteBet> {
+        merge_bets(&self.bets)
+    }
+}
+
+/// The log of the spins made on a table, oldest first. With a retention cap set only the most recent
+/// ```retention``` records are kept.
+#[derive(Debug, Clone)]
+pub struct SpinLog {
+    records: VecDeque<SpinRecord>,
+    next_id: u64,
+    retention: Option<usize>,
+}
+
+impl Default for SpinLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpinLog {
+    /// An empty log without a retention cap
+    pub fn new() -> Self {
+        Self {
+            records: VecDeque::new(),
+            next_id: 1,
+            retention: None,
+        }
+    }
+
+    pub fn retention(&self) -> Option<usize> {
+        self.retention
+    }
+
+    /// Caps the number of records kept, dropping the oldest ones straight away if needed
+    pub fn set_retention(&mut self, retention: Option<usize>) {
+        self.retention = retention;
+        self.trim();
+    }
+
+    /// Appends a record, assigning it the next id. Returns the number of old records dropped to respect the retention cap.
+    pub(crate) fn push(&mut self, mut record: SpinRecord) -> usize {
+        record.id = self.next_id;
+        self.next_id += 1;
+        self.records.push_back(record);
+        self.trim()
+    }
+
+    /// Replaces the log with previously recorded spins, oldest first. Numbering carries on after the last one.
+    pub(crate) fn restore(&mut self, records: Vec<SpinRecord>) {
+        if let Some(last) = records.last() {
+            self.next_id = last.id + 1;
+        }
+        self.records = records.into();
+        self.trim();
+    }
+
+    fn trim(&mut self) -> usize {
+        let excess = match self.retention {
+            Some(retention) => self.records.len().saturating_sub(retention),
+            None => 0,
+        };
+        self.records.drain(..excess);
+        excess
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// The id the next spin will be given
+    pub fn next_id(&self) -> u64 {
+        self.next_id
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &SpinRecord> {
+        self.records.iter()
+    }
+
+    pub fn last(&self) -> Option<&SpinRecord> {
+        self.records.back()
+    }
+
+    /// Looks up a spin by id. Spins dropped by the retention cap are no longer found.
+    pub fn get(&self, id: u64) -> Option<&SpinRecord> {
+        let first = self.records.front()?.id;
+        let index = id.checked_sub(first)?;
+        self.records.get(index as usize)
+    }
+
+    /// The spins made within a time range (both ends included)
+    pub fn between(&self, range: RangeInclusive<SystemTime>) -> impl Iterator<Item = &SpinRecord> {
+        self.records.iter().filter(move |record| range.contains(&record.timestamp))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+    use crate::outside::RedBlack;
+    use crate::rng::ScriptedSpins;
+    use crate::roulette::{Roulette, RouletteBetType};
+
+    #[test]
+    fn spins_are_recorded() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![17, 0]));
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Straight(17), 10),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Black), 20),
+        ];
+
+        r.spin(&bets).unwrap();
+        r.spin(&bets).unwrap();
+
+        let first = r.spin_log().get(1).unwrap();
+        assert_eq!(first.number, 17);
+        assert_eq!(first.bets, bets);
+        assert_eq!(first.rng, "scripted");
+        assert_eq!(first.total_wagered, 30);
+        assert_eq!(first.total_paid, 400);
+        assert_eq!(first.house_net, -370);
+
+        let second = r.spin_log().last().unwrap();
+        assert_eq!(second.id, 2);
+        assert_eq!(second.number, 0);
+        assert_eq!(second.house_net, 30);
+        assert!(r.spin_log().get(3).is_none());
+    }
+
+    #[test]
+    fn retention_cap() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![1, 2, 3, 4, 5]));
+        r.set_retention(Some(3));
+
+        for _ in 0..5 {
+            r.spin(&[]).unwrap();
+        }
+
+        assert_eq!(r.spin_log().len(), 3);
+        assert_eq!(r.history(), &[3, 4, 5]);
+        assert!(r.spin_log().get(2).is_none());
+        assert_eq!(r.spin_log().get(3).unwrap().number, 3);
+        assert_eq!(r.spin_log().next_id(), 6);
+
+        r.set_retention(Some(1));
+        assert_eq!(r.history(), &[5]);
+        assert_eq!(r.spin_log().records().map(|s| s.id).collect::<Vec<_>>(), vec![5]);
+    }
+
+    #[test]
+    fn time_range() {
+        let mut r = Roulette::new();
+        let start = SystemTime::now();
+        for _ in 0..3 {
+            r.spin(&[]).unwrap();
+        }
+        let end = SystemTime::now();
+
+        assert_eq!(r.spin_log().between(start..=end).count(), 3);
+        assert_eq!(r.spin_log().between(start - Duration::from_secs(60)..=start - Duration::from_secs(1)).count(), 0);
+    }
+}
@@ -0,0 +1,112 @@
+use rand::{Rng, RngCore, SeedableRng};
+use rand::rngs::StdRng;
+
+/// A source of randomness the wheel draws its winning pockets from.
+/// Every ```rand``` RNG (```ThreadRng```, a seeded ```StdRng```, ChaCha, ...) implements it out of the box.
+pub trait SpinRng {
+    /// Picks a pocket index in the range ```0..pockets```
+    fn pick_pocket(&mut self, pockets: usize) -> usize;
+
+    /// Describes where the next pocket comes from, for the spin records
+    fn provenance(&self) -> String;
+}
+
+impl<R: RngCore> SpinRng for R {
+    fn pick_pocket(&mut self, pockets: usize) -> usize {
+        self.gen_range(0, pockets)
+    }
+
+    fn provenance(&self) -> String {
+        std::any::type_name::<R>().to_string()
+    }
+}
+
+/// A ```StdRng``` seeded from a ```u64```, remembering its seed and how many pockets it drew so each spin can be
+/// reproduced from its record.
+#[derive(Debug, Clone)]
+pub struct SeededSpins {
+    rng: StdRng,
+    seed: u64,
+    draws: u64,
+}
+
+impl SeededSpins {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            draws: 0,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl SpinRng for SeededSpins {
+    fn pick_pocket(&mut self, pockets: usize) -> usize {
+        self.draws += 1;
+        self.rng.pick_pocket(pockets)
+    }
+
+    /// The seed and the number of draws before this one: replaying that many draws from the seed gives the pocket
+    fn provenance(&self) -> String {
+        format!("seeded seed={} draw={}", self.seed, self.draws)
+    }
+}
+
+/// Replays a fixed sequence of pockets, wrapping around once the end is reached.
+/// Useful for tests and for replaying a recorded session.
+#[derive(Debug, Clone)]
+pub struct ScriptedSpins {
+    pockets: Vec<u8>,
+    next: usize,
+}
+
+impl ScriptedSpins {
+    pub fn new(pockets: Vec<u8>) -> Self {
+        assert!(!pockets.is_empty(), "ScriptedSpins needs at least one pocket");
+        Self {
+            pockets,
+            next: 0,
+        }
+    }
+}
+
+impl SpinRng for ScriptedSpins {
+    fn pick_pocket(&mut self, pockets: usize) -> usize {
+        let pocket = self.pockets[self.next] as usize;
+        assert!(pocket < pockets, "Scripted pocket {} is not on a wheel of {} pockets", pocket, pockets);
+
+        self.next = (self.next + 1) % self.pockets.len();
+        pocket
+    }
+
+    fn provenance(&self) -> String {
+        String::from("scripted")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn scripted_pocket_off_the_wheel() {
+        let mut rng = ScriptedSpins::new(vec![37]);
+        rng.pick_pocket(37);
+    }
+
+    #[test]
+    fn seeded_spins_replay_from_their_provenance() {
+        let mut rng = SeededSpins::new(7);
+        let pockets: Vec<usize> = (0..5).map(|_| rng.pick_pocket(37)).collect();
+        assert_eq!(rng.provenance(), "seeded seed=7 draw=5");
+
+        let mut replay = SeededSpins::new(7);
+        let replayed: Vec<usize> = (0..5).map(|_| replay.pick_pocket(37)).collect();
+        assert_eq!(pockets, replayed);
+    }
+}
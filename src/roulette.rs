@@ -1,11 +1,31 @@
+use std::borrow::Cow;
 use std::fmt;
+use std::time::{Instant, SystemTime};
 use rand::prelude::*;
-
-#[derive(Debug, Copy, Clone)]
+use crate::announced::AnnouncedBet;
+use crate::liability::LiabilityTable;
+use crate::limits::{BetKind, TableLimits};
+use crate::money::{Currency, Money, MoneyError};
+use crate::outside::{Colour, Column, Dozen, Half, Parity, RedBlack};
+use crate::record::{SpinLog, SpinRecord};
+use crate::rng::{SeededSpins, SpinRng};
+use crate::round::{Round, RoundEvent, RoundPhase, RoundTimings};
+use crate::settlement::{BetId, Settlement, SettlementStatus, SpinOutcome};
+use crate::wheel::{WheelVariant, Pocket, DOUBLE_ZERO};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(from = "crate::schema::PlaceBetErrorRepr", into = "crate::schema::PlaceBetErrorRepr"))]
 pub enum PlaceBetError {
     InvalidBetOption(RouletteBet),
     MaxBetOnOption(RouletteBet, u64),
     MinBetNotSatisfied(RouletteBet, u64),
+    InvalidAnnouncedBet(AnnouncedBet, WheelVariant),
+    TableMaxExceeded(u64, u64),
+    PocketLiabilityExceeded(u8, u64, u64),
+    BettingClosed(Option<RoundPhase>),
+    RoundInProgress(u64),
+    CurrencyMismatch(RouletteBet, Currency),
+    PayoutOverflow(RouletteBet),
 }
 
 impl fmt::Display for PlaceBetError {
@@ -14,12 +34,21 @@ impl fmt::Display for PlaceBetError {
             PlaceBetError::InvalidBetOption(option) => write!(f, "Invalid Bet Option: {}", option),
             PlaceBetError::MaxBetOnOption(option, max) => write!(f, "Max bet of {} reached on option {}", max, option),
             PlaceBetError::MinBetNotSatisfied(option, min) => write!(f, "Minimum ({}) not met for option {}", min, option),
+            PlaceBetError::InvalidAnnouncedBet(bet, variant) => write!(f, "{} cannot be played on a {} wheel", bet, variant),
+            PlaceBetError::TableMaxExceeded(total, max) => write!(f, "Total of {} exceeds the table max of {}", total, max),
+            PlaceBetError::PocketLiabilityExceeded(pocket, liability, cap) => write!(f, "Payout of {} on pocket {} exceeds the liability cap of {}", liability, Pocket(*pocket), cap),
+            PlaceBetError::BettingClosed(Some(phase)) => write!(f, "Betting is closed ({})", phase),
+            PlaceBetError::BettingClosed(None) => write!(f, "No betting round is in progress"),
+            PlaceBetError::RoundInProgress(round) => write!(f, "Round {} is in progress, the wheel is spun by the round", round),
+            PlaceBetError::CurrencyMismatch(option, currency) => write!(f, "Option {} is not in the table currency ({})", option, currency),
+            PlaceBetError::PayoutOverflow(option) => write!(f, "The payout of option {} is too large", option),
         }
     }
 }
 
 /// Bet Types, defined by the type of bet, with the variant always being u8, but in some cases requiring an array of numbers to be inserted.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(try_from = "crate::schema::BetTypeRepr", into = "crate::schema::BetTypeRepr"))]
 pub enum RouletteBetType {
     /// Single number for the bet
     Straight(u8),
@@ -30,95 +59,180 @@ pub enum RouletteBetType {
     /// Numbers for the row chosen
     Street([u8; 3]),
 
-    /// Numbers covering either 0,1,2 or 0,2,3
+    /// Numbers covering either 0,1,2 or 0,2,3 (0,1,2 or 0,00,2 or 00,2,3 on American tables)
     Basket([u8; 3]),
 
-    /// Numbers covering 0, 1, 2, 3
+    /// Numbers covering 0, 1, 2, 3 (European only)
     Topline([u8; 4]),
 
+    /// Numbers covering 0, 00, 1, 2, 3 (American only)
+    Fiveline([u8; 5]),
+
     /// Number of 4 adjacent spots
     Corner([u8; 4]),
 
     /// Numbers covering 2 adjacent lines 
     Doubleline([u8; 6]),
 
-    /// 1 for 1-12, 2 for 13-24, 3 for 25-36
-    Dozens(u8),
+    /// 1-12, 13-24 or 25-36
+    Dozens(Dozen),
 
     /// Indicate the column based on the lowest number in that column (1, 2 or 3 to match columns under 34,35,36)
-    Columns(u8), 
+    Columns(Column),
 
-    /// 0 for even, 1 for odd
-    EvenOdd(u8),
+    EvenOdd(Parity),
 
-    /// 0 for 1-18, 1 for 19-36
-    Highlow(u8),
+    /// 1-18 or 19-36
+    Highlow(Half),
 
-    /// 0 for red, 1 for black
-    Redblack(u8),
+    /// Red or black
+    Redblack(RedBlack),
 }
 
 
+impl RouletteBetType {
+    /// Even money bets are the ones affected by the La Partage and En Prison rules
+    pub fn is_even_money(&self) -> bool {
+        matches!(self, RouletteBetType::EvenOdd(_) | RouletteBetType::Highlow(_) | RouletteBetType::Redblack(_))
+    }
+
+    /// The same bet with its numbers in ascending order. ```Split([11, 10])``` and ```Split([10, 11])``` are the
+    /// same spot on the table, and this is the form validation and reporting work with.
+    pub fn normalized(&self) -> Self {
+        let mut bet_type = *self;
+        match &mut bet_type {
+            RouletteBetType::Split(v) => v.sort_unstable(),
+            RouletteBetType::Street(v) => v.sort_unstable(),
+            RouletteBetType::Basket(v) => v.sort_unstable(),
+            RouletteBetType::Topline(v) => v.sort_unstable(),
+            RouletteBetType::Fiveline(v) => v.sort_unstable(),
+            RouletteBetType::Corner(v) => v.sort_unstable(),
+            RouletteBetType::Doubleline(v) => v.sort_unstable(),
+            _ => (),
+        }
+        bet_type
+    }
+
+    pub fn kind(&self) -> BetKind {
+        match self {
+            RouletteBetType::Straight(_) => BetKind::Straight,
+            RouletteBetType::Split(_) => BetKind::Split,
+            RouletteBetType::Street(_) => BetKind::Street,
+            RouletteBetType::Basket(_) => BetKind::Basket,
+            RouletteBetType::Topline(_) => BetKind::Topline,
+            RouletteBetType::Fiveline(_) => BetKind::Fiveline,
+            RouletteBetType::Corner(_) => BetKind::Corner,
+            RouletteBetType::Doubleline(_) => BetKind::Doubleline,
+            RouletteBetType::Dozens(_) => BetKind::Dozens,
+            RouletteBetType::Columns(_) => BetKind::Columns,
+            RouletteBetType::EvenOdd(_) => BetKind::EvenOdd,
+            RouletteBetType::Highlow(_) => BetKind::Highlow,
+            RouletteBetType::Redblack(_) => BetKind::Redblack,
+        }
+    }
+}
+
 impl fmt::Display for RouletteBetType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            RouletteBetType::Straight(v) => write!(f, "Straight({})", v),
-            RouletteBetType::Split(v) => write!(f, "Split({}, {})", v[0], v[1]),
+            RouletteBetType::Straight(v) => write!(f, "Straight({})", Pocket(*v)),
+            RouletteBetType::Split(v) => write!(f, "Split({}, {})", Pocket(v[0]), Pocket(v[1])),
             RouletteBetType::Street(v) => write!(f, "Street({}, {}, {})", v[0], v[1], v[2]),
-            RouletteBetType::Basket(v) => write!(f, "Basket({}, {}, {})", v[0], v[1], v[2]),
+            RouletteBetType::Basket(v) => write!(f, "Basket({}, {}, {})", Pocket(v[0]), Pocket(v[1]), Pocket(v[2])),
             RouletteBetType::Topline(v) => write!(f, "Topline({}, {}, {}, {})", v[0], v[1], v[2], v[3]),
+            RouletteBetType::Fiveline(v) => write!(f, "Fiveline({}, {}, {}, {}, {})", v[0], v[1], v[2], v[3], Pocket(v[4])),
             RouletteBetType::Corner(v) => write!(f, "Corner({}, {}, {}, {})", v[0], v[1], v[2], v[3]),
             RouletteBetType::Doubleline(v) => write!(f, "Doubleline({}, {}, {}, {}, {}, {})", v[0], v[1], v[2], v[3], v[4], v[5]),
             RouletteBetType::Dozens(v) => write!(f, "Dozens({})", v),
             RouletteBetType::Columns(v) => write!(f, "Columns({})", v),
-            RouletteBetType::EvenOdd(v) => write!(f, "EvenOdd({})", match v {
-                0 => "even",
-                1 => "odd",
-                _ => "INVALID",
-            }),
-            RouletteBetType::Highlow(v) => write!(f, "Highlow({})", match v {
-                0 => "1-18",
-                1 => "19-36",
-                _ => "INVALID",
-            }),
-            RouletteBetType::Redblack(v) => write!(f, "Redblack({})", match v {
-                0 => "red",
-                1 => "black",
-                _ => "INVALID",
-            })
+            RouletteBetType::EvenOdd(v) => write!(f, "EvenOdd({})", v),
+            RouletteBetType::Highlow(v) => write!(f, "Highlow({})", v),
+            RouletteBetType::Redblack(v) => write!(f, "Redblack({})", v),
         }
     }
 }
 
-/// Definition of a bet. 
-#[derive(Debug, Clone, Copy)]
+/// Definition of a bet. Bets expanded from an announced bet remember the announcement they belong to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "crate::schema::RouletteBetRepr"))]
 pub struct RouletteBet {
+    #[cfg_attr(feature = "serde", serde(flatten))]
     bet_type: RouletteBetType,
     wager: u64,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Currency::is_chips"))]
+    currency: Currency,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    announcement: Option<AnnouncedBet>,
 }
 
 impl fmt::Display for RouletteBet {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "type: {}, wager: {}", self.bet_type, self.wager)
+        write!(f, "type: {}, wager: {}", self.bet_type, self.stake())?;
+        if let Some(announcement) = self.announcement {
+            write!(f, " ({})", announcement)?;
+        }
+        Ok(())
     }
 }
 
 impl RouletteBet {
+    /// Creates a bet of ```wager``` chips. The numbers of the bet type are normalized, so they can be given in any order.
     pub fn new(bet_type: RouletteBetType, wager: u64) -> Self {
         Self {
-            bet_type,
+            bet_type: bet_type.normalized(),
             wager,
+            currency: Currency::Chips,
+            announcement: None,
+        }
+    }
+
+    /// A bet placed as part of an announced bet
+    pub fn announced(bet_type: RouletteBetType, wager: u64, announcement: AnnouncedBet) -> Self {
+        Self {
+            announcement: Some(announcement),
+            ..Self::new(bet_type, wager)
+        }
+    }
+
+    /// Creates a bet staking an amount of money
+    pub fn with_stake(bet_type: RouletteBetType, stake: Money) -> Self {
+        Self::new(bet_type, stake.minor_units()).in_currency(stake.currency())
+    }
+
+    /// The same bet, with the wager counted in minor units of ```currency```
+    pub fn in_currency(self, currency: Currency) -> Self {
+        Self {
+            currency,
+            ..self
         }
     }
 
     /// The win value is the multiplier. In other words if I bet on Even a bet of 10, i get 20. 
+    /// Saturates at ```u64::MAX```: bets whose win value doesn't fit are refused by ```Roulette::validate_bets```, see
+    /// ```checked_win_value``` for bets that were not validated.
     pub fn win_value(&self) -> u64 {
-        self.wager * match self.bet_type {
+        self.wager.saturating_mul(self.multiplier())
+    }
+
+    /// The win value, or ```None``` if it overflows
+    pub fn checked_win_value(&self) -> Option<u64> {
+        self.wager.checked_mul(self.multiplier())
+    }
+
+    /// The win value as an amount in the currency of the bet, failing if it overflows
+    pub fn win_amount(&self) -> Result<Money, MoneyError> {
+        self.stake().checked_mul(self.multiplier())
+    }
+
+    fn multiplier(&self) -> u64 {
+        match self.bet_type {
             RouletteBetType::Straight(_) => 36,
             RouletteBetType::Split(_) => 18,
             RouletteBetType::Street(_) => 12,
             RouletteBetType::Basket(_) => 12,
             RouletteBetType::Topline(_) => 9,
+            RouletteBetType::Fiveline(_) => 7,
             RouletteBetType::Corner(_) => 9,
             RouletteBetType::Doubleline(_) => 6,
             RouletteBetType::Dozens(_) => 3,
@@ -134,211 +248,263 @@ impl RouletteBet {
         self.bet_type
     }
 
+    /// The wager, in minor units of the currency of the bet
     pub fn wager(&self) -> u64 {
         self.wager
     }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn stake(&self) -> Money {
+        Money::new(self.wager, self.currency)
+    }
+
+    /// The announced bet this bet was expanded from, if any
+    pub fn announcement(&self) -> Option<AnnouncedBet> {
+        self.announcement
+    }
+}
+
+/// Merges the bets placed on the same spot into a single bet carrying their total wager, keeping the order in which
+/// each spot was first bet on. The merged bet keeps its announcement only if all the bets on the spot share it.
+/// Bets in different currencies are never merged.
+pub fn merge_bets(bets: &[RouletteBet]) -> Vec<RouletteBet> {
+    let mut merged: Vec<RouletteBet> = Vec::with_capacity(bets.len());
+
+    for bet in bets {
+        let bet_type = bet.bet_type.normalized();
+        match merged.iter_mut().find(|m| m.bet_type == bet_type && m.currency == bet.currency) {
+            Some(spot) => {
+                spot.wager = spot.wager.saturating_add(bet.wager);
+                if spot.announcement != bet.announcement {
+                    spot.announcement = None;
+                }
+            },
+            None => merged.push(RouletteBet {
+                bet_type,
+                ..*bet
+            }),
+        }
+    }
+
+    merged
+}
+
+/// The total of the wagers, in ```currency```. Fails with ```CurrencyMismatch``` for a bet in another currency, and
+/// with ```PayoutOverflow``` for the bet taking the total past ```u64::MAX```.
+pub fn total_wager(bets: &[RouletteBet], currency: Currency) -> Result<Money, PlaceBetError> {
+    bets.iter().try_fold(Money::zero(currency), |total, bet| {
+        total.checked_add(bet.stake()).map_err(|error| match error {
+            MoneyError::CurrencyMismatch(..) => PlaceBetError::CurrencyMismatch(*bet, currency),
+            _ => PlaceBetError::PayoutOverflow(*bet),
+        })
+    })
 }
 
 /// The result of a bet. Contains the bet itself and the winning amount. The responsibility of the winning is in the struct RouletteEvaluator
+/// Bets released from prison are owned by the result, as they are no longer part of the bets passed to the spin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RouletteBetResult<'a> {
-    bet: &'a RouletteBet,
+    bet: Cow<'a, RouletteBet>,
     win: u64,
+    imprisoned: bool,
 }
 
 impl<'a> RouletteBetResult<'a> {
     pub fn new(bet: &'a RouletteBet, win: u64) -> Self {
         Self {
-            bet,
-            win
+            bet: Cow::Borrowed(bet),
+            win,
+            imprisoned: false,
+        }
+    }
+
+    /// An even money bet that was put in prison by a zero, to be settled on the next spin
+    fn imprisoned(bet: &'a RouletteBet) -> Self {
+        Self {
+            bet: Cow::Borrowed(bet),
+            win: 0,
+            imprisoned: true,
         }
     }
 
-    pub fn bet(&self) -> &'a RouletteBet {
-        self.bet
+    /// A bet from a previous spin, released from prison
+    fn released(bet: RouletteBet, win: u64) -> Self {
+        Self {
+            bet: Cow::Owned(bet),
+            win,
+            imprisoned: false,
+        }
+    }
+
+    pub fn bet(&self) -> &RouletteBet {
+        &self.bet
     }
 
     pub fn win(&self) -> u64 {
         self.win
     }
+
+    /// Whether the bet was put in prison rather than settled
+    pub fn is_imprisoned(&self) -> bool {
+        self.imprisoned
+    }
+}
+
+/// What happens to even money bets when the ball lands on zero
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ZeroRule {
+    /// Even money bets lose
+    #[default]
+    Standard,
+
+    /// Half of the stake is returned
+    LaPartage,
+
+    /// The bet is imprisoned and settled by the next spin: if it wins the stake is returned, otherwise it is lost.
+    /// A bet that is still in prison when zero hits again is lost.
+    EnPrison,
 }
 
 /// This struct determines the winners (or loosers) in a set of input bets.
-struct RouletteEvaluator;
+pub(crate) struct RouletteEvaluator;
 
 impl RouletteEvaluator {
 
     // PR: Wouldn't it be a better idea to shift responsibility of colour in here? I would remove colour as a parameter
     // to the function and calculate it inside this method. 
-    pub fn calculate_winnings<'a>(winning_number: u8, bets: &'a Vec<RouletteBet>) -> Vec<RouletteBetResult<'a>> {
+    /// Fails with ```PayoutOverflow``` for the first winning bet whose payout doesn't fit in a ```u64```
+    pub fn calculate_winnings<'a>(variant: WheelVariant, zero_rule: ZeroRule, winning_number: u8, bets: &'a [RouletteBet]) -> Result<Vec<RouletteBetResult<'a>>, PlaceBetError> {
         let mut results = Vec::new();
 
-        let colour = RouletteEvaluator::get_number_colour(winning_number);
+        let colour = Colour::of(variant, winning_number);
+
+        // Outside bets all lose when the ball lands on a zero (0 or 00)
+        let outside = !variant.is_zero(winning_number);
 
         /// Takes a roulette bet and the function for that bet type to evaluate it. 
-        fn calc_win<'a, F>(bet: &'a RouletteBet, f: F) -> RouletteBetResult<'a> where F: FnOnce() -> bool {
-            RouletteBetResult::new(bet, if f() {
-                bet.win_value()
+        fn calc_win<'a, F>(bet: &'a RouletteBet, f: F) -> Result<RouletteBetResult<'a>, PlaceBetError> where F: FnOnce() -> bool {
+            let win = if f() {
+                bet.checked_win_value().ok_or(PlaceBetError::PayoutOverflow(*bet))?
             } else {
                 0
-            })
+            };
+            Ok(RouletteBetResult::new(bet, win))
         }
 
         for bet in bets {
+            // Zero rules replace the (losing) outcome of even money bets
+            if !outside && bet.bet_type().is_even_money() {
+                results.push(match zero_rule {
+                    ZeroRule::Standard => RouletteBetResult::new(bet, 0),
+                    ZeroRule::LaPartage => RouletteBetResult::new(bet, bet.wager() / 2),
+                    ZeroRule::EnPrison => RouletteBetResult::imprisoned(bet),
+                });
+                continue;
+            }
+
             results.push(
                 match bet.bet_type() {
                     RouletteBetType::Straight(v) => calc_win(bet, || v == winning_number), // Just match the number. 
 
-                    // Determine if the winning number falls in the chosen dozen (1 for 1-12, 2 for 13-24, 3 for 25-36)
-                    // PR: Wouldn't it be simpler to do (winning_number-1)/12 == v - 1 ? 
-                    // For example, if dozen 3 is chosen and 25 comes up: 25 - 1 / 12 = 3 - 1 // We have a winner 
-                    // For example, if dozen 1 is chosen and 1 comes up: 1 - 1 / 12 = 1 - 1 // We have a winner
-                    // For example, if dozen 1 is chosen and 0 comes up: 0 - 1 / 12 <> 1 - 1 // We have a loser
-                    RouletteBetType::Dozens(v) => calc_win(bet, || (winning_number > 0 && (winning_number-1)/12 == v - 1)), 
-                   
+                    // Determine if the winning number falls in the chosen dozen. Zeros are in no dozen.
+                    RouletteBetType::Dozens(v) => calc_win(bet, || Dozen::of(winning_number) == Some(v)),
 
                     // Indicate the column based on the lowest number in that column (1, 2 or 3 to match columns under 34,35,36)
-                    // PR: Wouldn't it be easier if we do: winning_number > 0 && winning_number % 3 = (v % 3)
-                    // For example: if column 1 is chosen, and 7 comes up 7 % 3 = 1 % 3
-                    // For example: if column 3 is chosen, and 33 comes up 33 % 3 = 3 % 3
-                    RouletteBetType::Columns(v) => calc_win(bet, || (winning_number > 0 && winning_number % 3 == v % 3)),
-                        
-                    // Match modulo 2 of winning number and whether it was even (0) or odd(1) 
-                    // PR: v%2 is superflous. we can just have (winning_number % 2) == v 
-                    RouletteBetType::EvenOdd(v) => calc_win(bet, || (winning_number % 2) == v), 
+                    RouletteBetType::Columns(v) => calc_win(bet, || Column::of(winning_number) == Some(v)),
 
+                    // Zero is neither even nor odd.
+                    RouletteBetType::EvenOdd(v) => calc_win(bet, || Parity::of(winning_number) == Some(v)),
 
-                    // 0 = low, 1 = high. Low is between 1 - 18, high 19 - 36. Zero not included (neither high nor low)
-                    RouletteBetType::Highlow(v) => calc_win(bet, || { 
-                        (v == 0 && winning_number >= 1 && winning_number <= 18) || 
-                        (v == 1 && winning_number >= 19 && winning_number <= 36)
-                    }),
+                    // Low is between 1 - 18, high 19 - 36. Zero not included (neither high nor low)
+                    RouletteBetType::Highlow(v) => calc_win(bet, || Half::of(winning_number) == Some(v)),
 
-                    // Just match on colour
-                    RouletteBetType::Redblack(v) => calc_win(bet, || v == colour),
+                    // Just match on colour (zeros are green)
+                    RouletteBetType::Redblack(v) => calc_win(bet, || Colour::from(v) == colour),
 
                     // In all the following types we just determine whether the number exists within the input array of chosen numbers
                     RouletteBetType::Split(v) => calc_win(bet, || v.contains(&winning_number)),
                     RouletteBetType::Street(v) => calc_win(bet, || v.contains(&winning_number)),
                     RouletteBetType::Basket(v) => calc_win(bet, || v.contains(&winning_number)),
                     RouletteBetType::Topline(v) => calc_win(bet, || v.contains(&winning_number)),
+                    RouletteBetType::Fiveline(v) => calc_win(bet, || v.contains(&winning_number)),
                     RouletteBetType::Corner(v) => calc_win(bet, || v.contains(&winning_number)),
                     RouletteBetType::Doubleline(v) => calc_win(bet, || v.contains(&winning_number)),
-                }
+                }?
             )
         }
 
-        results
-    }
-
-    fn get_number_colour(number: u8) -> u8 {
-        match number {
-            // PR: what about zero? I would add zero as another colour, as this can affect badly the redBlack bet type.
-            0 => 2,
-            1 | 3 | 5 | 7 | 9 | 12 | 14 | 16 | 18 | 19 | 21 | 23 | 25 | 27 | 30 | 32 | 34 | 36 => 0,
-            _ => 1,
-        }
+        Ok(results)
     }
 }
 
+/// Splits involving a zero pocket, per wheel variant
+const EUROPEAN_ZERO_SPLITS: [[u8; 2]; 3] = [[0, 1], [0, 2], [0, 3]];
+const AMERICAN_ZERO_SPLITS: [[u8; 2]; 3] = [[0, 1], [0, DOUBLE_ZERO], [3, DOUBLE_ZERO]];
+
+/// Three number bets touching the zero pocket(s), per wheel variant
+const EUROPEAN_BASKETS: [[u8; 3]; 2] = [[0, 1, 2], [0, 2, 3]];
+const AMERICAN_BASKETS: [[u8; 3]; 3] = [[0, 1, 2], [0, 2, DOUBLE_ZERO], [2, 3, DOUBLE_ZERO]];
+
 /// The roulette engine implementation. All the bet history is stored here. 
+/// The winning pockets are drawn from ```R```, which defaults to the thread local RNG.
 #[derive(Debug, Clone)]
-pub struct Roulette {
+pub struct Roulette<R = ThreadRng> {
+    variant: WheelVariant,
+    zero_rule: ZeroRule,
+    prison: Vec<Settlement>,
     history: Vec<u8>,
-    min_bet_size: u64,
-    rng: ThreadRng,
+    log: SpinLog,
+    limits: TableLimits,
+    liability_cap: Option<u64>,
+    round: Option<Round>,
+    rng: R,
 }
 
-impl Roulette {
-    pub fn new() -> Self {
-        Self {
-            history: Vec::new(),
-            min_bet_size: 1,
-            rng: thread_rng(),
-        }
-    }
-
-    /// The roulette spin. Takes a list of bets in, picks the winning number, and returns the results (and any errors)
-    pub fn spin<'a>(&mut self, bets: &'a Vec<RouletteBet>) -> Result<(u8, Vec<RouletteBetResult<'a>>), Vec<PlaceBetError>> {
-        self.validate_bets(bets)?;
-
-        // spin
-        let number = self.rng.gen_range(0, 36);
-        self.history.push(number);
-
-        Ok((number, RouletteEvaluator::calculate_winnings(number, &bets)))
+impl Default for Roulette {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    pub fn history(&self) -> &[u8] {
-        self.history.as_slice()
-    }
-
-    fn validate_bets(&self, bets: &Vec<RouletteBet>) -> Result<(), Vec<PlaceBetError>> {
-        let mut errors = Vec::new();
-
-        // check for errors
-        for bet in bets {
-            if !Self::validate_bet_option(bet.bet_type()) {
-                errors.push(PlaceBetError::InvalidBetOption(bet.clone()))
-            } else if !self.validate_bet_size(bet) {
-                errors.push(PlaceBetError::MinBetNotSatisfied(bet.clone(), self.min_bet_size * Self::min_bet_for_option(bet.bet_type())))
-            }
-        }
-
-        if errors.len() == 0 {
-            Ok(())
-        } else {
-            Err(errors)
-        }
+impl Roulette {
+    /// Creates a roulette with a single zero (European) wheel
+    pub fn new() -> Self {
+        Self::with_variant(WheelVariant::European)
     }
 
-    fn min_bet_for_option(bet_type: RouletteBetType) -> u64 {
-        match bet_type {
-            RouletteBetType::Straight(_) => 1,
-            RouletteBetType::Split(_) => 1,
-            RouletteBetType::Street(_) => 1,
-            RouletteBetType::Basket(_) => 1,
-            RouletteBetType::Topline(_) => 1,
-            RouletteBetType::Corner(_) => 1,
-            RouletteBetType::Doubleline(_) => 1,
-            RouletteBetType::Dozens(_) => 1,
-            RouletteBetType::Columns(_) => 1,
-            RouletteBetType::EvenOdd(_) => 1,
-            RouletteBetType::Highlow(_) => 1,
-            RouletteBetType::Redblack(_) => 1,
-        }
+    pub fn with_variant(variant: WheelVariant) -> Self {
+        Self::with_rng(variant, thread_rng())
     }
 
-    /// Checks that a ```RouletteBetType``` is valid and can be played
-    /// *NOTE*: The logic expects the elements in a &[u8] array of values to be sorted in ascending order
-    fn validate_bet_option(bet_type: RouletteBetType) -> bool {
-        match bet_type {
-            // Staight numbers are easy: any number (including zero) smaller or equal to 36.
-            RouletteBetType::Straight(v) => v <= 36,
-
+    /// Checks that a ```RouletteBetType``` is valid and can be played on the given wheel variant.
+    /// The numbers can be in any order, the bet type is normalized first.
+    pub(crate) fn validate_bet_option(variant: WheelVariant, bet_type: RouletteBetType) -> bool {
+        match bet_type.normalized() {
+            // Staight numbers are easy: any pocket on the wheel (including zero, and double zero on American wheels).
+            RouletteBetType::Straight(v) => variant.has_pocket(v),
 
             RouletteBetType::Split(v) => {
-
-                // range and duplicate check
-                (v[0] != v[1] && (v[0] <= 35 && v[1] <= 36) && v[1] > v[0]) 
-                &&
-                // splits with zero can only be combined with 1,2,3
-                (
-                    v[0] == 0 && (v[1] == 1 || v[1] == 2 || v[1] == 3)
-                ) 
-                ||
-                // numbers 1 to 33
-                ((v[0] > 0 && v[0] <= 33) && 
+                if v[0] == v[1] {
+                    false
+                } else if variant.is_zero(v[0]) || variant.is_zero(v[1]) {
+                    // splits with zero can only be combined with the numbers bordering it
+                    match variant {
+                        WheelVariant::European => EUROPEAN_ZERO_SPLITS.contains(&v),
+                        WheelVariant::American => AMERICAN_ZERO_SPLITS.contains(&v),
+                    }
+                } else {
+                    v[1] <= 36 &&
                     (
-                        // right edge
-                        (v[1] % 3 == 0 && v[1] - v[0] == 1 || v[1] - v[0] == 3) ||
-                        // left edge
-                        (v[0] % 3 == 1 && v[1] - v[0] == 1 || v[1] - v[0] == 3)
-                        
+                        // horizontal neighbours, as long as the first number is not on the right edge
+                        (v[1] - v[0] == 1 && v[0] % 3 != 0) ||
+                        // vertical neighbours
+                        v[1] - v[0] == 3
                     )
-                ) 
-                ||
-                // bottom edge (34, 35, 36)
-                (v[0] >= 34 && v[1] - v[0] == 1)
+                }
             }
 
             // A street has to always start at the first column, and the other two numbers need to be 1 value apart.
@@ -350,21 +516,26 @@ impl Roulette {
                 v[2] - v[1] == 1
             }
 
-            // Numbers covering either 0,1,2 or 0,2,3
-            RouletteBetType::Basket(v) => {
-                v[0] == 0 &&
-                ((v[1] == 1 && v[2] == 2) ||
-                (v[1] == 2 && v[2] == 3))
+            // Numbers covering either 0,1,2 or 0,2,3 (0,1,2 or 0,00,2 or 00,2,3 on American tables)
+            RouletteBetType::Basket(v) => match variant {
+                WheelVariant::European => EUROPEAN_BASKETS.contains(&v),
+                WheelVariant::American => AMERICAN_BASKETS.contains(&v),
             }
 
-            // Topline is always exactly 0123
+            // Topline is always exactly 0123, and only exists on a single zero table
             RouletteBetType::Topline(v) => {
-                v[0] == 0 && v[1] == 1 && v[2] == 2 && v[3] == 3
+                variant == WheelVariant::European && v == [0, 1, 2, 3]
+            }
+
+            // Fiveline is always exactly 0,00,1,2,3, and only exists on a double zero table
+            RouletteBetType::Fiveline(v) => {
+                variant == WheelVariant::American && v == [0, 1, 2, 3, DOUBLE_ZERO]
             }
 
-            // Corners: Cannot start with zero, they can only start on 1st, 2nd column, rows should have a difference of 3, columns a difference of 1
+            // Corners: Cannot start with zero or go past 36, they can only start on 1st, 2nd column, rows should have a difference of 3, columns a difference of 1
             RouletteBetType::Corner(v) => {
                 v[0] > 0 &&
+                v[3] <= 36 &&
                 (v[0] % 3 != 0) &&
                 v[1] - v[0] == 1 &&
                 v[3] - v[2] == 1 &&
@@ -379,29 +550,336 @@ impl Roulette {
                 let mut slice2: [u8; 3] = Default::default();
                 slice1.copy_from_slice(&v[0..=2]);
                 slice2.copy_from_slice(&v[3..=5]);
-                Self::validate_bet_option(RouletteBetType::Street(slice1)) &&
-                Self::validate_bet_option(RouletteBetType::Street(slice2))
+                Self::validate_bet_option(variant, RouletteBetType::Street(slice1)) &&
+                Self::validate_bet_option(variant, RouletteBetType::Street(slice2))
             },
 
-            // Can only have values of 1,2,3
-            RouletteBetType::Dozens(v) => v >= 1 && v <= 3,
-            RouletteBetType::Columns(v) => v >= 1 && v <= 3,
+            // Outside bets are valid by construction
+            RouletteBetType::Dozens(_) | RouletteBetType::Columns(_) | RouletteBetType::EvenOdd(_) | RouletteBetType::Highlow(_) |
+            RouletteBetType::Redblack(_) => true,
+        }
+    }
+}
+
+impl Roulette<SeededSpins> {
+    /// Creates a roulette whose spins are fully determined by ```seed```. Identical seeds always produce an identical history.
+    /// The seed is written to the record of every spin.
+    pub fn from_seed(variant: WheelVariant, seed: u64) -> Self {
+        Self::with_rng(variant, SeededSpins::new(seed))
+    }
+}
+
+impl<R: SpinRng> Roulette<R> {
+    /// Creates a roulette drawing its winning pockets from ```rng```
+    pub fn with_rng(variant: WheelVariant, rng: R) -> Self {
+        Self {
+            variant,
+            zero_rule: ZeroRule::Standard,
+            prison: Vec::new(),
+            history: Vec::new(),
+            log: SpinLog::new(),
+            limits: TableLimits::default(),
+            liability_cap: None,
+            round: None,
+            rng,
+        }
+    }
+
+    /// The roulette spin. Takes a list of bets in, picks the winning number, and returns the results (and any errors)
+    /// Under ```ZeroRule::EnPrison``` the results also include the bets released from prison by this spin.
+    /// While rounds are running the wheel is only spun by ```tick```, and this fails with ```RoundInProgress```.
+    pub fn spin<'a>(&mut self, bets: &'a [RouletteBet]) -> Result<(u8, Vec<RouletteBetResult<'a>>), Vec<PlaceBetError>> {
+        let outcome = self.spin_settled(bets)?;
+        let results = outcome.settlements()
+            .iter()
+            .enumerate()
+            .map(|(i, settlement)| match bets.get(i) {
+                Some(bet) if settlement.status() == SettlementStatus::Imprisoned => RouletteBetResult::imprisoned(bet),
+                Some(bet) => RouletteBetResult::new(bet, settlement.payout()),
+                None => RouletteBetResult::released(*settlement.bet(), settlement.payout()),
+            })
+            .collect();
+        Ok((outcome.number(), results))
+    }
+
+    /// Same as ```spin```, but the results are owned settlements, identified by the spin id and the index of the bet.
+    /// Bets released from prison keep the id of the spin they were placed on.
+    pub fn spin_settled(&mut self, bets: &[RouletteBet]) -> Result<SpinOutcome, Vec<PlaceBetError>> {
+        if let Some(round) = &self.round {
+            return Err(vec![PlaceBetError::RoundInProgress(round.number)]);
+        }
+        self.validate_bets(bets)?;
+        Ok(self.draw(bets))
+    }
+
+    /// Spins and settles bets that have already been validated
+    fn draw(&mut self, bets: &[RouletteBet]) -> SpinOutcome {
+        let provenance = self.rng.provenance();
+        let number = self.rng.pick_pocket(self.variant.pocket_count()) as u8;
+        self.history.push(number);
+        let spin = self.log.next_id();
+
+        let mut settlements: Vec<Settlement> = RouletteEvaluator::calculate_winnings(self.variant, self.zero_rule, number, bets)
+            .expect("validated bets pay out without overflowing")
+            .iter()
+            .enumerate()
+            .map(|(index, result)| Settlement::new(BetId::new(spin, index), result))
+            .collect();
+
+        // settle the bets imprisoned by the previous spin: a win only returns the stake
+        let prisoners = std::mem::take(&mut self.prison);
+        self.prison.extend(settlements.iter().filter(|s| s.status() == SettlementStatus::Imprisoned).copied());
+        for prisoner in prisoners {
+            let won = RouletteEvaluator::calculate_winnings(self.variant, ZeroRule::Standard, number, std::slice::from_ref(prisoner.bet()))
+                .expect("validated bets pay out without overflowing")[0].win() > 0;
+            let result = RouletteBetResult::released(*prisoner.bet(), if won { prisoner.stake() } else { 0 });
+            settlements.push(Settlement::new(prisoner.bet_id(), &result));
+        }
+
+        self.record(provenance, number, bets, &settlements);
+        SpinOutcome::new(spin, number, Colour::of(self.variant, number), settlements)
+    }
+
+    /// Starts running timed rounds, the first one opening for bets at ```now```. While rounds are running, bets are
+    /// placed with ```place_bets``` and the wheel is spun by ```tick```.
+    pub fn start_rounds(&mut self, timings: RoundTimings, now: Instant) -> RoundEvent {
+        self.round = Some(Round::new(1, timings, now));
+        RoundEvent::BettingOpen(1)
+    }
+
+    /// Stops running rounds. Returns the bets of the round in progress that were not drawn yet, to be handed back.
+    pub fn stop_rounds(&mut self) -> Vec<RouletteBet> {
+        self.round.take().map(|round| round.bets).unwrap_or_default()
+    }
+
+    /// The number and phase of the round in progress, if rounds are running
+    pub fn round(&self) -> Option<(u64, RoundPhase)> {
+        self.round.as_ref().map(|round| (round.number, round.phase))
+    }
+
+    /// The bets placed in the round in progress
+    pub fn round_bets(&self) -> &[RouletteBet] {
+        self.round.as_ref().map(|round| round.bets.as_slice()).unwrap_or(&[])
+    }
 
-            // Can only have values of 0, 1
-            RouletteBetType::EvenOdd(v) => v <= 1,
-            RouletteBetType::Highlow(v) => v <= 1,
-            RouletteBetType::Redblack(v) => v <= 1,
+    /// Adds bets to the round in progress. They are validated together with the bets already placed in the round.
+    pub fn place_bets(&mut self, bets: &[RouletteBet]) -> Result<(), Vec<PlaceBetError>> {
+        let placed = match &self.round {
+            Some(round) => &round.bets,
+            None => return Err(vec![PlaceBetError::BettingClosed(None)]),
+        };
+        let all: Vec<RouletteBet> = placed.iter().chain(bets).copied().collect();
+        self.validate_bets(&all)?;
+
+        if let Some(round) = &mut self.round {
+            round.bets = all;
+        }
+        Ok(())
+    }
+
+    /// Moves the round in progress through the phases that are over at ```now```, drawing the ball when betting
+    /// closes. A tick goes no further than the opening of the next round.
+    pub fn tick(&mut self, now: Instant) -> Vec<RoundEvent> {
+        let mut events = Vec::new();
+        let mut round = match self.round.take() {
+            Some(round) => round,
+            None => return events,
+        };
+
+        while now >= round.due() {
+            let event = match round.phase {
+                RoundPhase::Open => {
+                    round.enter(RoundPhase::LastCall);
+                    RoundEvent::LastCall(round.number)
+                },
+                RoundPhase::LastCall => {
+                    round.enter(RoundPhase::NoMoreBets);
+                    RoundEvent::NoMoreBets(round.number)
+                },
+                RoundPhase::NoMoreBets => {
+                    round.enter(RoundPhase::BallLanded);
+                    let outcome = self.draw(&std::mem::take(&mut round.bets));
+                    let event = RoundEvent::BallLanded(round.number, outcome.number(), outcome.colour());
+                    round.outcome = Some(outcome);
+                    event
+                },
+                RoundPhase::BallLanded => {
+                    round.enter(RoundPhase::Settled);
+                    RoundEvent::Settled(round.number, round.outcome.take().expect("the ball has landed"))
+                },
+                RoundPhase::Settled => {
+                    round = Round::new(round.number + 1, round.timings, round.due());
+                    events.push(RoundEvent::BettingOpen(round.number));
+                    break;
+                },
+            };
+            events.push(event);
         }
+
+        self.round = Some(round);
+        events
+    }
+
+    fn record(&mut self, rng: String, number: u8, bets: &[RouletteBet], settlements: &[Settlement]) {
+        let total_wagered: u64 = bets.iter().map(|bet| bet.wager()).sum();
+        let total_paid: u64 = settlements.iter().map(|settlement| settlement.payout()).sum();
+
+        let dropped = self.log.push(SpinRecord {
+            id: 0,
+            timestamp: SystemTime::now(),
+            variant: self.variant,
+            rng,
+            number,
+            bets: bets.to_vec(),
+            total_wagered,
+            total_paid,
+            house_net: total_wagered as i64 - total_paid as i64,
+        });
+        self.history.drain(..dropped);
+    }
+
+    /// The winning numbers, oldest first. Trimmed along with the spin log when a retention cap is set.
+    pub fn history(&self) -> &[u8] {
+        self.history.as_slice()
+    }
+
+    /// The full records of the spins made on this table
+    pub fn spin_log(&self) -> &SpinLog {
+        &self.log
     }
 
-    fn validate_bet_size(&self, bet: &RouletteBet) -> bool {
-        Self::min_bet_for_option(bet.bet_type()) & self.min_bet_size <= bet.wager()
+    /// Reloads spins recorded earlier (e.g. read back from a ```Journal```), replacing the history and the spin log
+    pub fn restore(&mut self, records: Vec<SpinRecord>) {
+        self.log.restore(records);
+        self.history = self.log.records().map(|record| record.number).collect();
+    }
+
+    /// Caps the number of spins kept in the history and the spin log. ```None``` keeps every spin.
+    pub fn set_retention(&mut self, retention: Option<usize>) {
+        self.log.set_retention(retention);
+        let excess = self.history.len() - self.log.len();
+        self.history.drain(..excess);
+    }
+
+    pub fn variant(&self) -> WheelVariant {
+        self.variant
+    }
+
+    pub fn zero_rule(&self) -> ZeroRule {
+        self.zero_rule
+    }
+
+    /// Sets the rule applied to even money bets when zero hits. Bets already in prison are still settled by the next spin.
+    pub fn set_zero_rule(&mut self, zero_rule: ZeroRule) {
+        self.zero_rule = zero_rule;
+    }
+
+    pub fn limits(&self) -> &TableLimits {
+        &self.limits
+    }
+
+    pub fn set_limits(&mut self, limits: TableLimits) {
+        self.limits = limits;
+    }
+
+    pub fn liability_cap(&self) -> Option<u64> {
+        self.liability_cap
+    }
+
+    /// Caps the payout any single pocket can cost the house on a spin. Bet sets going over it are rejected.
+    pub fn set_liability_cap(&mut self, cap: Option<u64>) {
+        self.liability_cap = cap;
+    }
+
+    /// The even money bets waiting in prison for the next spin
+    pub fn imprisoned(&self) -> &[Settlement] {
+        self.prison.as_slice()
+    }
+
+    /// The source the winning pockets are drawn from
+    pub fn rng(&self) -> &R {
+        &self.rng
+    }
+
+    pub fn rng_mut(&mut self) -> &mut R {
+        &mut self.rng
+    }
+
+    /// Checks a set of bets against the table (bet options, limits and liability cap) without spinning
+    pub fn validate_bets(&self, bets: &[RouletteBet]) -> Result<(), Vec<PlaceBetError>> {
+        let mut errors = Vec::new();
+
+        if let Some(round) = &self.round {
+            if !round.phase.accepts_bets() {
+                errors.push(PlaceBetError::BettingClosed(Some(round.phase)))
+            }
+        }
+
+        // check for errors, with the bets on the same spot counted as one position
+        let merged = merge_bets(bets);
+        for bet in &merged {
+            if !Roulette::validate_bet_option(self.variant, bet.bet_type()) {
+                errors.push(PlaceBetError::InvalidBetOption(*bet))
+            } else if let Some(error) = self.limits.check_bet(bet) {
+                errors.push(error)
+            }
+        }
+
+        if let Some(error) = self.limits.check_total(bets) {
+            errors.push(error)
+        }
+
+        // every payout has to fit in a u64, and the payouts of a spin are netted against the stakes as signed amounts,
+        // so the total staked, and the total paid on any pocket, have to fit in an i64
+        let mut overflowing: Vec<PlaceBetError> = merged.iter()
+            .filter(|bet| bet.checked_win_value().is_none())
+            .map(|bet| PlaceBetError::PayoutOverflow(*bet))
+            .collect();
+        let mut staked = 0u64;
+        if let Some(bet) = merged.iter().find(|bet| {
+            staked = staked.saturating_add(bet.wager);
+            staked > i64::MAX as u64
+        }) {
+            let error = PlaceBetError::PayoutOverflow(*bet);
+            if !overflowing.contains(&error) {
+                overflowing.push(error);
+            }
+        }
+        if !overflowing.is_empty() {
+            errors.extend(overflowing);
+        } else {
+            match LiabilityTable::new(self.variant, self.zero_rule, &merged) {
+                Ok(liabilities) => {
+                    let (pocket, liability) = liabilities.worst();
+                    if liability > i64::MAX as u64 {
+                        // every bet paying out on that pocket takes part in the overflow
+                        let results = RouletteEvaluator::calculate_winnings(self.variant, self.zero_rule, pocket, &merged).unwrap_or_default();
+                        errors.extend(results.iter().filter(|result| result.win() > 0).map(|result| PlaceBetError::PayoutOverflow(*result.bet())));
+                    } else if let Some(cap) = self.liability_cap {
+                        if liability > cap {
+                            errors.push(PlaceBetError::PocketLiabilityExceeded(pocket, liability, cap))
+                        }
+                    }
+                },
+                Err(error) => errors.push(error),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
     use super::*;
+    use crate::limits::BetLimit;
+    use crate::rng::ScriptedSpins;
+    use std::convert::TryFrom;
 
     #[test]
     fn spin_and_history_test() {
@@ -409,7 +887,7 @@ mod test {
         let mut history = Vec::new();
 
         for _ in 0..10 {
-            match r.spin(&vec![]) {
+            match r.spin(&[]) {
                 Ok((num, _results)) => {
                     history.push(num);
                 },
@@ -436,6 +914,49 @@ mod test {
             assert_eq!(rbs.win_value(), i*36);
             assert_eq!(rbc.win_value(), i*9)
         }
+
+        let huge = RouletteBet::new(RouletteBetType::Straight(1), u64::MAX / 10);
+        assert_eq!(huge.checked_win_value(), None);
+        assert_eq!(huge.win_value(), u64::MAX);
+        assert_eq!(huge.win_amount(), Err(MoneyError::Overflow));
+        assert_eq!(RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 21).checked_win_value(), Some(42));
+    }
+
+    #[test]
+    fn payout_overflow() {
+        let mut r = Roulette::new();
+        r.set_liability_cap(Some(1000));
+
+        // one error per bet
+        let huge = RouletteBet::new(RouletteBetType::Straight(1), u64::MAX / 10);
+        let huger = RouletteBet::new(RouletteBetType::Straight(5), u64::MAX / 5);
+        assert_eq!(r.spin(&[huge, huger]).err(), Some(vec![PlaceBetError::PayoutOverflow(huge), PlaceBetError::PayoutOverflow(huger)]));
+
+        // red and black never both win, so their payouts aren't added up
+        let red = RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), i64::MAX as u64 / 2);
+        let black = RouletteBet::new(RouletteBetType::Redblack(RedBlack::Black), i64::MAX as u64 / 2);
+        assert_eq!(r.validate_bets(&[red, black]), Err(vec![PlaceBetError::PocketLiabilityExceeded(1, i64::MAX as u64 - 1, 1000)]));
+
+        // red and odd both win on 1, where the total payout doesn't fit in an i64
+        let odd = RouletteBet::new(RouletteBetType::EvenOdd(Parity::Odd), 1);
+        assert_eq!(r.validate_bets(&[red, black, odd]), Err(vec![PlaceBetError::PayoutOverflow(red), PlaceBetError::PayoutOverflow(odd)]));
+
+        // the stakes are netted against the payouts too, so their total has to fit in an i64 as well
+        let zero = RouletteBet::new(RouletteBetType::Straight(0), i64::MAX as u64 / 36);
+        r.set_liability_cap(None);
+        assert_eq!(r.spin(&[red, black, zero]).err(), Some(vec![PlaceBetError::PayoutOverflow(zero)]));
+        r.set_liability_cap(Some(1000));
+
+        // bets on the same spot are added up first
+        let straight = RouletteBet::new(RouletteBetType::Straight(2), u64::MAX / 40);
+        assert_eq!(r.validate_bets(&[straight, straight]), Err(vec![PlaceBetError::PayoutOverflow(RouletteBet::new(RouletteBetType::Straight(2), u64::MAX / 40 * 2))]));
+        assert_eq!(total_wager(&[straight, straight], Currency::Chips), Ok(Money::chips(u64::MAX / 40 * 2)));
+        assert_eq!(total_wager(&[huger; 6], Currency::Chips), Err(PlaceBetError::PayoutOverflow(huger)));
+        assert_eq!(total_wager(&[straight.in_currency(Currency::Eur)], Currency::Eur), Ok(Money::new(u64::MAX / 40, Currency::Eur)));
+        assert_eq!(total_wager(&[straight, straight.in_currency(Currency::Eur)], Currency::Chips),
+            Err(PlaceBetError::CurrencyMismatch(straight.in_currency(Currency::Eur), Currency::Chips)));
+        assert_eq!(RouletteEvaluator::calculate_winnings(WheelVariant::European, ZeroRule::Standard, 5, &[huger]), Err(PlaceBetError::PayoutOverflow(huger)));
+        assert!(r.history().is_empty());
     }
 
     #[test]
@@ -449,14 +970,14 @@ mod test {
             RouletteBet::new(RouletteBetType::Topline([0, 1, 2, 3]), wager),
             RouletteBet::new(RouletteBetType::Corner([1, 2, 4, 5]), wager),
             RouletteBet::new(RouletteBetType::Doubleline([1, 2, 3, 4, 5, 6]), wager),
-            RouletteBet::new(RouletteBetType::Dozens(1), wager),
-            RouletteBet::new(RouletteBetType::Columns(1), wager),
-            RouletteBet::new(RouletteBetType::EvenOdd(0), wager),
-            RouletteBet::new(RouletteBetType::Highlow(0), wager),
-            RouletteBet::new(RouletteBetType::Redblack(1), wager), // PR: Error here. 0 is red, not black. whilst 2 is red. Fixed this.
+            RouletteBet::new(RouletteBetType::Dozens(Dozen::First), wager),
+            RouletteBet::new(RouletteBetType::Columns(Column::First), wager),
+            RouletteBet::new(RouletteBetType::EvenOdd(Parity::Even), wager),
+            RouletteBet::new(RouletteBetType::Highlow(Half::Low), wager),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Black), wager), // PR: Error here. 0 is red, not black. whilst 2 is red. Fixed this.
         ];
 
-        let results = RouletteEvaluator::calculate_winnings(2, &bets);
+        let results = RouletteEvaluator::calculate_winnings(WheelVariant::European, ZeroRule::Standard, 2, &bets).unwrap();
         let mut winnings = 0;
 
         for res in results {
@@ -466,6 +987,7 @@ mod test {
                 RouletteBetType::Street(_) => assert_eq!(res.win(), 120),
                 RouletteBetType::Basket(_) => assert_eq!(res.win(), 120),
                 RouletteBetType::Topline(_) => assert_eq!(res.win(), 90),
+                RouletteBetType::Fiveline(_) => unreachable!(),
                 RouletteBetType::Corner(_) => assert_eq!(res.win(), 90),
                 RouletteBetType::Doubleline(_) => assert_eq!(res.win(), 60),
                 RouletteBetType::Dozens(_) => assert_eq!(res.win(), 30),
@@ -483,14 +1005,14 @@ mod test {
     #[test]
     fn valid_bettype_straights() {
         for i in 0..37 {
-            assert_eq!(Roulette::validate_bet_option(RouletteBetType::Straight(i)), true);
+            assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Straight(i)), true);
         }
     }
 
     #[test]
     fn invalid_bettype_straights() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Straight(37)), false);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Straight(129)), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Straight(37)), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Straight(129)), false);
     }
 
     #[test]
@@ -498,7 +1020,7 @@ mod test {
         for i in 1..36 {
             if i % 3 != 0 {
                 let bt = RouletteBetType::Split([i, i+1]);
-                let res = Roulette::validate_bet_option(bt);
+                let res = Roulette::validate_bet_option(WheelVariant::European, bt);
                 if !res { println!("invalid bettype: {}", bt)}
                 assert_eq!(res, true);
             }
@@ -507,14 +1029,14 @@ mod test {
 
     #[test]
     fn valid_bettype_split_vertical() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Split([0, 1])), true);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Split([0, 2])), true);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Split([0, 3])), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Split([0, 1])), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Split([0, 2])), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Split([0, 3])), true);
 
         for i in 1..36 {
             if i+3 <= 36 {
                 let bt = RouletteBetType::Split([i, i+3]);
-                let res = Roulette::validate_bet_option(bt);
+                let res = Roulette::validate_bet_option(WheelVariant::European, bt);
                 if !res { panic!("invalid bettype: {}", bt)}
                 assert_eq!(res, true);
             }
@@ -524,12 +1046,12 @@ mod test {
     #[test]
     fn invalid_bettype_split() {
         // invalid duplicate split
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Split([1, 1])), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Split([1, 1])), false);
 
         // invalid 0 splits
         for i in 4..37 {
             let bt = RouletteBetType::Split([0, i]);
-            let res = Roulette::validate_bet_option(bt);
+            let res = Roulette::validate_bet_option(WheelVariant::European, bt);
             if res { panic!("Unexpected valid bettype: {}", bt)}
             assert_eq!(res, false);
         }
@@ -537,11 +1059,11 @@ mod test {
         // all other invalid splits
         for i in 1..37 {
             let bt = RouletteBetType::Split([i, i+2]);
-            assert_eq!(Roulette::validate_bet_option(bt), false);
+            assert_eq!(Roulette::validate_bet_option(WheelVariant::European, bt), false);
             
             for j in 4..37 {
                 let bt = RouletteBetType::Split([i, i+j]);
-                assert_eq!(Roulette::validate_bet_option(bt), false);
+                assert_eq!(Roulette::validate_bet_option(WheelVariant::European, bt), false);
             }
         }
     }
@@ -550,7 +1072,7 @@ mod test {
     fn valid_bettype_street() {
         for i in 1..35 {
             if i%3 == 1 {
-                assert_eq!(Roulette::validate_bet_option(RouletteBetType::Street([i, i+1, i+2])), true);
+                assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Street([i, i+1, i+2])), true);
             }
         }
     }
@@ -559,43 +1081,43 @@ mod test {
     fn invalid_bettype_street() {
         for i in 1..35 {
             if i%3 != 1 {
-                assert_eq!(Roulette::validate_bet_option(RouletteBetType::Street([i, i+1, i+2])), false);
+                assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Street([i, i+1, i+2])), false);
             }
         }
     }
 
     #[test]
     fn valid_bettype_basket() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Basket([0, 1, 2])), true);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Basket([0, 2, 3])), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Basket([0, 1, 2])), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Basket([0, 2, 3])), true);
     }
 
     #[test]
     fn invalid_bettype_basket() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Basket([0, 1, 3])), false);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Basket([0, 1, 4])), false);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Basket([1, 2, 3])), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Basket([0, 1, 3])), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Basket([0, 1, 4])), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Basket([1, 2, 3])), false);
 
     }
 
     #[test]
     fn valid_bettype_topline() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Topline([0, 1, 2, 3])), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Topline([0, 1, 2, 3])), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Topline([0, 2, 3, 1])), true);
     }
 
     #[test]
     fn invalid_bettype_topline() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Topline([1, 2, 3, 4])), false);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Topline([0, 2, 3, 4])), false);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Topline([0, 2, 3, 5])), false);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Topline([0, 2, 3, 1])), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Topline([1, 2, 3, 4])), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Topline([0, 2, 3, 4])), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Topline([0, 2, 3, 5])), false);
     }
 
     #[test]
     fn valid_bettype_corner() {
         for i in 1..33 {
             if i % 3 != 0 {
-                assert_eq!(Roulette::validate_bet_option(RouletteBetType::Corner([i, i+1, i+3, i+4])), true);
+                assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Corner([i, i+1, i+3, i+4])), true);
             }
         }
     }
@@ -604,18 +1126,22 @@ mod test {
     fn invalid_bettype_corner() {
         for i in 1..33 {
             if i % 3 == 0 {
-                assert_eq!(Roulette::validate_bet_option(RouletteBetType::Corner([i, i+1, i+3, i+4])), false);
+                assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Corner([i, i+1, i+3, i+4])), false);
             } else {
-                assert_eq!(Roulette::validate_bet_option(RouletteBetType::Corner([i, i+1, i+2, i+3])), false);
+                assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Corner([i, i+1, i+2, i+3])), false);
             }
         }
+
+        // past the end of the layout
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Corner([35, 36, 38, 39])), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Corner([34, 35, 37, 38])), false);
     }
 
     #[test]
     fn valid_bettype_doubleline() {
         for i in 1..32 {
             if i % 3 == 1 {
-                assert_eq!(Roulette::validate_bet_option(RouletteBetType::Doubleline([i, i+1, i+2, i+3, i+4, i+5])), true);
+                assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Doubleline([i, i+1, i+2, i+3, i+4, i+5])), true);
             }
         }
     }
@@ -624,7 +1150,7 @@ mod test {
     fn invalid_bettype_doubleline() {
         for i in 1..37 {
             if i % 3 != 1 {
-                assert_eq!(Roulette::validate_bet_option(RouletteBetType::Doubleline([i, i+1, i+2, i+3, i+4, i+5])), false);
+                assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Doubleline([i, i+1, i+2, i+3, i+4, i+5])), false);
             }
         }
     }
@@ -632,65 +1158,65 @@ mod test {
     #[test]
     fn valid_bettype_dozens() {
         for i in 1..4 {
-            assert_eq!(Roulette::validate_bet_option(RouletteBetType::Dozens(i)), true);
+            assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Dozens(Dozen::try_from(i).unwrap())), true);
         }
     }
 
     #[test]
     fn invalid_bettype_dozens() {
         for i in 4..37 {
-            assert_eq!(Roulette::validate_bet_option(RouletteBetType::Dozens(i)), false);
+            assert_eq!(Dozen::try_from(i).is_err(), true);
         }
     }
 
     #[test]
     fn valid_bettype_columns() {
-        for i in 4..37 {
-            assert_eq!(Roulette::validate_bet_option(RouletteBetType::Dozens(i)), false);
+        for i in 1..4 {
+            assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Columns(Column::try_from(i).unwrap())), true);
         }
     }
 
     #[test]
     fn invalid_bettype_columns() {
         for i in 4..37 {
-            assert_eq!(Roulette::validate_bet_option(RouletteBetType::Dozens(i)), false);
+            assert_eq!(Column::try_from(i).is_err(), true);
         }
     }
 
     #[test]
     fn valid_bettype_oddeven() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::EvenOdd(0)), true);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::EvenOdd(1)), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::EvenOdd(Parity::Even)), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::EvenOdd(Parity::Odd)), true);
     }
 
     #[test]
     fn invalid_bettype_oddeven() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::EvenOdd(2)), false);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::EvenOdd(3)), false);
+        assert_eq!(Parity::try_from(2).is_err(), true);
+        assert_eq!(Parity::try_from(3).is_err(), true);
     }
 
     #[test]
     fn valid_bettype_highlow() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Highlow(0)), true);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Highlow(1)), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Highlow(Half::Low)), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Highlow(Half::High)), true);
     }
 
     #[test]
     fn invalid_bettype_highlow() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Highlow(2)), false);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Highlow(3)), false);
+        assert_eq!(Half::try_from(2).is_err(), true);
+        assert_eq!(Half::try_from(3).is_err(), true);
     }
 
     #[test]
     fn valid_bettype_redblack() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Redblack(0)), true);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Redblack(1)), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Redblack(RedBlack::Red)), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Redblack(RedBlack::Black)), true);
     }
 
     #[test]
     fn invalid_bettype_redblack() {
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Redblack(2)), false);
-        assert_eq!(Roulette::validate_bet_option(RouletteBetType::Redblack(3)), false);
+        assert_eq!(RedBlack::try_from(2).is_err(), true);
+        assert_eq!(RedBlack::try_from(3).is_err(), true);
     }
 
     #[test]
@@ -706,7 +1232,207 @@ mod test {
         ];
 
         for bet in vdz {
-            assert_eq!(Roulette::validate_bet_option(bet), true);
+            assert_eq!(Roulette::validate_bet_option(WheelVariant::European, bet), true);
+        }
+    }
+
+    #[test]
+    fn rouletteeval_calc_winnings_double_zero() {
+        let wager = 10;
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Straight(DOUBLE_ZERO), wager),
+            RouletteBet::new(RouletteBetType::Split([0, DOUBLE_ZERO]), wager),
+            RouletteBet::new(RouletteBetType::Basket([2, 3, DOUBLE_ZERO]), wager),
+            RouletteBet::new(RouletteBetType::Fiveline([0, 1, 2, 3, DOUBLE_ZERO]), wager),
+            RouletteBet::new(RouletteBetType::Columns(Column::First), wager),
+            RouletteBet::new(RouletteBetType::EvenOdd(Parity::Odd), wager),
+            RouletteBet::new(RouletteBetType::Highlow(Half::High), wager),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Black), wager),
+        ];
+
+        let results = RouletteEvaluator::calculate_winnings(WheelVariant::American, ZeroRule::Standard, DOUBLE_ZERO, &bets).unwrap();
+        let wins: Vec<u64> = results.iter().map(|r| r.win()).collect();
+        assert_eq!(wins, vec![360, 180, 120, 70, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn rouletteeval_zero_loses_outside_bets() {
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::EvenOdd(Parity::Even), 10),
+            RouletteBet::new(RouletteBetType::Columns(Column::Third), 10),
+            RouletteBet::new(RouletteBetType::Dozens(Dozen::First), 10),
+        ];
+
+        for res in RouletteEvaluator::calculate_winnings(WheelVariant::European, ZeroRule::Standard, 0, &bets).unwrap() {
+            assert_eq!(res.win(), 0);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn spin_american_range() {
+        let mut r = Roulette::with_variant(WheelVariant::American);
+        for _ in 0..1000 {
+            let (num, _) = r.spin(&[]).unwrap();
+            assert!(num <= DOUBLE_ZERO);
+        }
+    }
+
+    #[test]
+    fn valid_bettype_american() {
+        let valid = vec![
+            RouletteBetType::Straight(DOUBLE_ZERO),
+            RouletteBetType::Split([0, 1]),
+            RouletteBetType::Split([0, DOUBLE_ZERO]),
+            RouletteBetType::Split([3, DOUBLE_ZERO]),
+            RouletteBetType::Basket([0, 1, 2]),
+            RouletteBetType::Basket([0, 2, DOUBLE_ZERO]),
+            RouletteBetType::Basket([2, 3, DOUBLE_ZERO]),
+            RouletteBetType::Fiveline([0, 1, 2, 3, DOUBLE_ZERO]),
+        ];
+
+        for bet in valid {
+            assert_eq!(Roulette::validate_bet_option(WheelVariant::American, bet), true);
+        }
+    }
+
+    #[test]
+    fn invalid_bettype_american() {
+        let invalid = vec![
+            RouletteBetType::Straight(38),
+            RouletteBetType::Split([0, 3]),
+            RouletteBetType::Split([1, DOUBLE_ZERO]),
+            RouletteBetType::Basket([0, 2, 3]),
+            RouletteBetType::Topline([0, 1, 2, 3]),
+            RouletteBetType::Corner([34, 35, 37, DOUBLE_ZERO]),
+        ];
+
+        for bet in invalid {
+            assert_eq!(Roulette::validate_bet_option(WheelVariant::American, bet), false);
+        }
+    }
+
+    #[test]
+    fn invalid_bettype_double_zero_on_european() {
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Straight(DOUBLE_ZERO)), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Split([36, DOUBLE_ZERO])), false);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Fiveline([0, 1, 2, 3, DOUBLE_ZERO])), false);
+    }
+
+    #[test]
+    fn seeded_spins_are_reproducible() {
+        let mut a = Roulette::from_seed(WheelVariant::European, 42);
+        let mut b = Roulette::from_seed(WheelVariant::European, 42);
+        let mut c = Roulette::from_seed(WheelVariant::European, 43);
+
+        for _ in 0..100 {
+            a.spin(&[]).unwrap();
+            b.spin(&[]).unwrap();
+            c.spin(&[]).unwrap();
+        }
+
+        assert_eq!(a.history(), b.history());
+        assert_ne!(a.history(), c.history());
+        assert_eq!(a.spin_log().last().unwrap().rng, "seeded seed=42 draw=99");
+    }
+
+    #[test]
+    fn scripted_spins() {
+        let mut r = Roulette::with_rng(WheelVariant::American, ScriptedSpins::new(vec![17, DOUBLE_ZERO, 0]));
+        let bets = vec![RouletteBet::new(RouletteBetType::Straight(17), 10)];
+
+        let (num, results) = r.spin(&bets).unwrap();
+        assert_eq!(num, 17);
+        assert_eq!(results[0].win(), 360);
+
+        for _ in 0..4 {
+            r.spin(&bets).unwrap();
+        }
+        assert_eq!(r.history(), &[17, DOUBLE_ZERO, 0, 17, DOUBLE_ZERO]);
+    }
+
+    #[test]
+    fn la_partage() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![0]));
+        r.set_zero_rule(ZeroRule::LaPartage);
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 10),
+            RouletteBet::new(RouletteBetType::Highlow(Half::High), 15),
+            RouletteBet::new(RouletteBetType::Dozens(Dozen::First), 10),
+        ];
+
+        let (_, results) = r.spin(&bets).unwrap();
+        let wins: Vec<u64> = results.iter().map(|r| r.win()).collect();
+        assert_eq!(wins, vec![5, 7, 0]);
+        assert!(r.imprisoned().is_empty());
+    }
+
+    #[test]
+    fn en_prison() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![0, 10, 0, 11, 0, 0, 4]));
+        r.set_zero_rule(ZeroRule::EnPrison);
+        let bets = vec![RouletteBet::new(RouletteBetType::EvenOdd(Parity::Even), 10)];
+
+        // zero imprisons the bet, the next spin wins back the stake
+        let (_, results) = r.spin(&bets).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_imprisoned());
+        assert_eq!(r.imprisoned().len(), 1);
+
+        let (_, results) = r.spin(&[]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].is_imprisoned());
+        assert_eq!(results[0].win(), 10);
+        assert!(r.imprisoned().is_empty());
+
+        // imprisoned then lost
+        r.spin(&bets).unwrap();
+        let (_, results) = r.spin(&[]).unwrap();
+        assert_eq!(results[0].win(), 0);
+
+        // imprisoned, then zero again loses the bet
+        r.spin(&bets).unwrap();
+        let (_, results) = r.spin(&bets).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_imprisoned());
+        assert_eq!(results[1].win(), 0);
+        assert!(!results[1].is_imprisoned());
+
+        let (_, results) = r.spin(&[]).unwrap();
+        assert_eq!(results[0].win(), 10);
+    }
+
+    #[test]
+    fn normalization() {
+        assert_eq!(RouletteBet::new(RouletteBetType::Split([11, 10]), 5).bet_type(), RouletteBetType::Split([10, 11]));
+        assert_eq!(RouletteBetType::Corner([11, 10, 8, 7]).normalized(), RouletteBetType::Corner([7, 8, 10, 11]));
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Corner([11, 10, 8, 7])), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::American, RouletteBetType::Basket([DOUBLE_ZERO, 0, 2])), true);
+        assert_eq!(Roulette::validate_bet_option(WheelVariant::European, RouletteBetType::Split([10, 10])), false);
+    }
+
+    #[test]
+    fn merged_bets() {
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Split([10, 11]), 20),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 5),
+            RouletteBet::new(RouletteBetType::Split([11, 10]), 15),
+            RouletteBet::announced(RouletteBetType::Straight(17), 5, AnnouncedBet::Orphelins),
+            RouletteBet::announced(RouletteBetType::Straight(17), 5, AnnouncedBet::Orphelins),
+        ];
+
+        assert_eq!(merge_bets(&bets), vec![
+            RouletteBet::new(RouletteBetType::Split([10, 11]), 35),
+            RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 5),
+            RouletteBet::announced(RouletteBetType::Straight(17), 10, AnnouncedBet::Orphelins),
+        ]);
+
+        // the limits apply to the spot, not to each chip placed on it
+        let mut r = Roulette::new();
+        let mut limits = TableLimits::default();
+        limits.set_kind_limit(BetKind::Split, BetLimit::new(1, 30));
+        r.set_limits(limits);
+
+        let errors = r.validate_bets(&bets).unwrap_err();
+        assert_eq!(errors, vec![PlaceBetError::MaxBetOnOption(RouletteBet::new(RouletteBetType::Split([10, 11]), 35), 30)]);
+    }
+}
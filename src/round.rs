@@ -0,0 +1,265 @@
+//! Timed betting rounds, for live and automated tables.
+//!
+//! A round goes through five phases, each lasting the time set in ```RoundTimings```:
+//!
+//! ```text
+//! Open -> LastCall -> NoMoreBets -> BallLanded -> Settled -> (next round) Open
+//! ```
+//!
+//! Bets are taken while the round is open and during the last call. The ball is drawn when the no more bets phase
+//! ends, and the payouts are settled once the result has been shown. Every transition emits a ```RoundEvent```.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+use crate::outside::Colour;
+use crate::roulette::RouletteBet;
+use crate::settlement::SpinOutcome;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum RoundPhase {
+    /// Bets are taken
+    Open,
+
+    /// Bets are still taken, betting is about to close
+    LastCall,
+
+    /// The ball is spinning, no bets are taken
+    NoMoreBets,
+
+    /// The winning number is known and shown
+    BallLanded,
+
+    /// The bets have been paid out
+    Settled,
+}
+
+impl RoundPhase {
+    pub fn accepts_bets(&self) -> bool {
+        matches!(self, RoundPhase::Open | RoundPhase::LastCall)
+    }
+}
+
+impl fmt::Display for RoundPhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match self {
+            RoundPhase::Open => "betting open",
+            RoundPhase::LastCall => "last call",
+            RoundPhase::NoMoreBets => "no more bets",
+            RoundPhase::BallLanded => "ball landed",
+            RoundPhase::Settled => "settled",
+        })
+    }
+}
+
+/// How long each phase of a round lasts
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RoundTimings {
+    pub open: Duration,
+    pub last_call: Duration,
+    pub no_more_bets: Duration,
+    pub ball_landed: Duration,
+    pub settled: Duration,
+}
+
+impl Default for RoundTimings {
+    fn default() -> Self {
+        Self {
+            open: Duration::from_secs(15),
+            last_call: Duration::from_secs(5),
+            no_more_bets: Duration::from_secs(10),
+            ball_landed: Duration::from_secs(5),
+            settled: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RoundTimings {
+    pub fn duration(&self, phase: RoundPhase) -> Duration {
+        match phase {
+            RoundPhase::Open => self.open,
+            RoundPhase::LastCall => self.last_call,
+            RoundPhase::NoMoreBets => self.no_more_bets,
+            RoundPhase::BallLanded => self.ball_landed,
+            RoundPhase::Settled => self.settled,
+        }
+    }
+}
+
+/// Emitted when a round enters a phase. The first field is the round number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoundEvent {
+    BettingOpen(u64),
+    LastCall(u64),
+    NoMoreBets(u64),
+    BallLanded(u64, u8, Colour),
+    Settled(u64, SpinOutcome),
+}
+
+impl RoundEvent {
+    pub fn round(&self) -> u64 {
+        match self {
+            RoundEvent::BettingOpen(round)
+            | RoundEvent::LastCall(round)
+            | RoundEvent::NoMoreBets(round)
+            | RoundEvent::BallLanded(round, _, _)
+            | RoundEvent::Settled(round, _) => *round,
+        }
+    }
+
+    /// The phase the round entered
+    pub fn phase(&self) -> RoundPhase {
+        match self {
+            RoundEvent::BettingOpen(_) => RoundPhase::Open,
+            RoundEvent::LastCall(_) => RoundPhase::LastCall,
+            RoundEvent::NoMoreBets(_) => RoundPhase::NoMoreBets,
+            RoundEvent::BallLanded(_, _, _) => RoundPhase::BallLanded,
+            RoundEvent::Settled(_, _) => RoundPhase::Settled,
+        }
+    }
+}
+
+/// The round in progress at a table
+#[derive(Debug, Clone)]
+pub(crate) struct Round {
+    pub(crate) number: u64,
+    pub(crate) phase: RoundPhase,
+    pub(crate) timings: RoundTimings,
+
+    /// When the current phase started
+    pub(crate) since: Instant,
+
+    /// The bets placed in this round, until the ball is drawn
+    pub(crate) bets: Vec<RouletteBet>,
+
+    /// The outcome of the draw, held until the round is settled
+    pub(crate) outcome: Option<SpinOutcome>,
+}
+
+impl Round {
+    pub(crate) fn new(number: u64, timings: RoundTimings, since: Instant) -> Self {
+        Self {
+            number,
+            phase: RoundPhase::Open,
+            timings,
+            since,
+            bets: Vec::new(),
+            outcome: None,
+        }
+    }
+
+    /// When the current phase is over
+    pub(crate) fn due(&self) -> Instant {
+        self.since + self.timings.duration(self.phase)
+    }
+
+    /// Moves on to ```phase```, timed from the end of the previous one rather than from when it was noticed
+    pub(crate) fn enter(&mut self, phase: RoundPhase) {
+        self.since = self.due();
+        self.phase = phase;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rng::ScriptedSpins;
+    use crate::roulette::{PlaceBetError, Roulette, RouletteBetType};
+    use crate::settlement::SettlementStatus;
+    use crate::wheel::WheelVariant;
+
+    fn secs(n: u64) -> Duration {
+        Duration::from_secs(n)
+    }
+
+    #[test]
+    fn round_goes_through_its_phases() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![17, 4]));
+        let start = Instant::now();
+        assert_eq!(r.start_rounds(RoundTimings::default(), start), RoundEvent::BettingOpen(1));
+        assert_eq!(r.round(), Some((1, RoundPhase::Open)));
+
+        r.place_bets(&[RouletteBet::new(RouletteBetType::Straight(17), 10)]).unwrap();
+        assert!(r.tick(start + secs(14)).is_empty());
+
+        // last call still takes bets
+        assert_eq!(r.tick(start + secs(15)), vec![RoundEvent::LastCall(1)]);
+        r.place_bets(&[RouletteBet::new(RouletteBetType::Straight(4), 10)]).unwrap();
+        assert_eq!(r.round_bets().len(), 2);
+
+        assert_eq!(r.tick(start + secs(20)), vec![RoundEvent::NoMoreBets(1)]);
+        let late = RouletteBet::new(RouletteBetType::Straight(5), 10);
+        assert_eq!(r.place_bets(&[late]), Err(vec![PlaceBetError::BettingClosed(Some(RoundPhase::NoMoreBets))]));
+        assert!(r.spin(&[late]).is_err());
+        assert!(r.history().is_empty());
+
+        assert_eq!(r.tick(start + secs(30)), vec![RoundEvent::BallLanded(1, 17, Colour::Black)]);
+        assert_eq!(r.history(), &[17]);
+
+        match &r.tick(start + secs(35))[..] {
+            [RoundEvent::Settled(1, outcome)] => {
+                let statuses: Vec<SettlementStatus> = outcome.settlements().iter().map(|s| s.status()).collect();
+                assert_eq!(statuses, vec![SettlementStatus::Win, SettlementStatus::Lose]);
+                assert_eq!(outcome.total_payout(), 360);
+            },
+            events => panic!("unexpected events {:?}", events),
+        }
+
+        assert_eq!(r.tick(start + secs(40)), vec![RoundEvent::BettingOpen(2)]);
+        assert_eq!(r.round(), Some((2, RoundPhase::Open)));
+        assert!(r.round_bets().is_empty());
+    }
+
+    #[test]
+    fn late_ticks_catch_up_to_the_next_round() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![0, 1]));
+        let start = Instant::now();
+        r.start_rounds(RoundTimings::default(), start);
+
+        let phases: Vec<RoundPhase> = r.tick(start + secs(100)).iter().map(|e| e.phase()).collect();
+        assert_eq!(phases, vec![
+            RoundPhase::LastCall, RoundPhase::NoMoreBets, RoundPhase::BallLanded, RoundPhase::Settled, RoundPhase::Open,
+        ]);
+
+        // the second round is timed from when the first one should have ended, not from the late tick
+        assert_eq!(r.round(), Some((2, RoundPhase::Open)));
+        assert_eq!(r.tick(start + secs(72)), vec![
+            RoundEvent::LastCall(2), RoundEvent::NoMoreBets(2), RoundEvent::BallLanded(2, 1, Colour::Red),
+        ]);
+    }
+
+    #[test]
+    fn only_the_round_spins_the_wheel() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![9]));
+        let start = Instant::now();
+        r.start_rounds(RoundTimings::default(), start);
+        let bet = RouletteBet::new(RouletteBetType::Straight(9), 10);
+
+        assert_eq!(r.spin(&[bet]).err(), Some(vec![PlaceBetError::RoundInProgress(1)]));
+        r.tick(start + secs(15));
+        assert_eq!(r.round(), Some((1, RoundPhase::LastCall)));
+        assert_eq!(r.spin_settled(&[bet]), Err(vec![PlaceBetError::RoundInProgress(1)]));
+        assert!(r.history().is_empty());
+        assert!(r.spin_log().is_empty());
+
+        r.place_bets(&[bet]).unwrap();
+        r.tick(start + secs(30));
+        assert_eq!(r.history(), &[9]);
+        assert_eq!(r.spin_log().last().unwrap().bets, vec![bet]);
+    }
+
+    #[test]
+    fn stopping_hands_back_the_bets() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![0]));
+        assert_eq!(r.place_bets(&[]), Err(vec![PlaceBetError::BettingClosed(None)]));
+
+        r.start_rounds(RoundTimings::default(), Instant::now());
+        let bet = RouletteBet::new(RouletteBetType::Straight(1), 5);
+        r.place_bets(&[bet]).unwrap();
+        assert_eq!(r.stop_rounds(), vec![bet]);
+        assert_eq!(r.round(), None);
+
+        // without rounds, bets are spun directly again
+        assert!(r.spin(&[bet]).is_ok());
+    }
+}
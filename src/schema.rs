@@ -0,0 +1,349 @@
+//! JSON schema of the bets, results and errors, enabled by the ```serde``` feature.
+//!
+//! Every tagged object carries its kind in ```"type"```, in snake case. Double zero is pocket 37.
+//!
+//! Bet types:
+//!
+//! ```text
+//! {"type":"straight","number":17}
+//! {"type":"split","numbers":[10,11]}          street, basket, topline, fiveline, corner and doubleline alike,
+//!                                              with 3, 3, 4, 5, 4 and 6 numbers
+//! {"type":"dozens","value":1}                  columns, even_odd, highlow and redblack alike
+//! ```
+//!
+//! The outside bets keep their numeric codes: dozens and columns 1 to 3, even 0 / odd 1, low 0 / high 1 and
+//! red 0 / black 1. A code that doesn't name a selector fails to deserialize.
+//!
+//! A bet is its bet type plus the wager, its currency unless it is in chips, and the announcement it was expanded from
+//! if any. The wager is in minor units of the currency (cents, pence, satoshis), the currency one of ```EUR```,
+//! ```GBP```, ```USD``` and ```BTC```:
+//!
+//! ```text
+//! {"type":"split","numbers":[16,17],"wager":10}
+//! {"type":"redblack","value":0,"wager":1250,"currency":"EUR"}
+//! {"type":"straight","number":17,"wager":5,"announcement":{"type":"neighbours","number":17,"count":2}}
+//! ```
+//!
+//! Announced bets: ```voisins_du_zero```, ```tiers```, ```orphelins```, ```jeu_zero```,
+//! ```{"type":"neighbours","number":17,"count":2}```, ```{"type":"finale_en_plein","digit":7}```,
+//! ```{"type":"finale_a_cheval","digits":[3,6]}```.
+//!
+//! Results: ```{"bet":{...},"win":360,"imprisoned":false}```
+//!
+//! Settlements, with a status of ```win```, ```lose```, ```push``` or ```imprisoned```:
+//!
+//! ```text
+//! {"bet_id":{"spin":1,"index":0},"bet":{...},"payout":360,"status":"win"}
+//! {"spin":1,"number":17,"colour":"black","settlements":[...]}
+//! ```
+//!
+//! Errors:
+//!
+//! ```text
+//! {"type":"invalid_bet_option","bet":{...}}
+//! {"type":"max_bet_on_option","bet":{...},"max":100}
+//! {"type":"min_bet_not_satisfied","bet":{...},"min":5}
+//! {"type":"invalid_announced_bet","announcement":{...},"variant":"american"}
+//! {"type":"table_max_exceeded","total":1600,"max":1500}
+//! {"type":"pocket_liability_exceeded","pocket":17,"liability":3600,"cap":3000}
+//! {"type":"betting_closed","phase":"no_more_bets"}       phase is null when no round is in progress
+//! {"type":"round_in_progress","round":3}                 spinning directly while rounds are running
+//! {"type":"currency_mismatch","bet":{...},"currency":"EUR"}
+//! {"type":"payout_overflow","bet":{...}}
+//! ```
+
+use std::convert::{TryFrom, TryInto};
+use serde::{Deserialize, Serialize};
+use crate::announced::AnnouncedBet;
+use crate::money::Currency;
+use crate::outside::InvalidSelector;
+use crate::roulette::{RouletteBet, RouletteBetType, PlaceBetError};
+use crate::round::RoundPhase;
+use crate::wheel::WheelVariant;
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum BetTypeRepr {
+    Straight { number: u8 },
+    Split { numbers: [u8; 2] },
+    Street { numbers: [u8; 3] },
+    Basket { numbers: [u8; 3] },
+    Topline { numbers: [u8; 4] },
+    Fiveline { numbers: [u8; 5] },
+    Corner { numbers: [u8; 4] },
+    Doubleline { numbers: [u8; 6] },
+    Dozens { value: u8 },
+    Columns { value: u8 },
+    EvenOdd { value: u8 },
+    Highlow { value: u8 },
+    Redblack { value: u8 },
+}
+
+impl From<RouletteBetType> for BetTypeRepr {
+    fn from(bet_type: RouletteBetType) -> Self {
+        match bet_type {
+            RouletteBetType::Straight(number) => BetTypeRepr::Straight { number },
+            RouletteBetType::Split(numbers) => BetTypeRepr::Split { numbers },
+            RouletteBetType::Street(numbers) => BetTypeRepr::Street { numbers },
+            RouletteBetType::Basket(numbers) => BetTypeRepr::Basket { numbers },
+            RouletteBetType::Topline(numbers) => BetTypeRepr::Topline { numbers },
+            RouletteBetType::Fiveline(numbers) => BetTypeRepr::Fiveline { numbers },
+            RouletteBetType::Corner(numbers) => BetTypeRepr::Corner { numbers },
+            RouletteBetType::Doubleline(numbers) => BetTypeRepr::Doubleline { numbers },
+            RouletteBetType::Dozens(value) => BetTypeRepr::Dozens { value: value.into() },
+            RouletteBetType::Columns(value) => BetTypeRepr::Columns { value: value.into() },
+            RouletteBetType::EvenOdd(value) => BetTypeRepr::EvenOdd { value: value.into() },
+            RouletteBetType::Highlow(value) => BetTypeRepr::Highlow { value: value.into() },
+            RouletteBetType::Redblack(value) => BetTypeRepr::Redblack { value: value.into() },
+        }
+    }
+}
+
+impl TryFrom<BetTypeRepr> for RouletteBetType {
+    type Error = InvalidSelector;
+
+    fn try_from(repr: BetTypeRepr) -> Result<Self, Self::Error> {
+        Ok(match repr {
+            BetTypeRepr::Straight { number } => RouletteBetType::Straight(number),
+            BetTypeRepr::Split { numbers } => RouletteBetType::Split(numbers),
+            BetTypeRepr::Street { numbers } => RouletteBetType::Street(numbers),
+            BetTypeRepr::Basket { numbers } => RouletteBetType::Basket(numbers),
+            BetTypeRepr::Topline { numbers } => RouletteBetType::Topline(numbers),
+            BetTypeRepr::Fiveline { numbers } => RouletteBetType::Fiveline(numbers),
+            BetTypeRepr::Corner { numbers } => RouletteBetType::Corner(numbers),
+            BetTypeRepr::Doubleline { numbers } => RouletteBetType::Doubleline(numbers),
+            BetTypeRepr::Dozens { value } => RouletteBetType::Dozens(value.try_into()?),
+            BetTypeRepr::Columns { value } => RouletteBetType::Columns(value.try_into()?),
+            BetTypeRepr::EvenOdd { value } => RouletteBetType::EvenOdd(value.try_into()?),
+            BetTypeRepr::Highlow { value } => RouletteBetType::Highlow(value.try_into()?),
+            BetTypeRepr::Redblack { value } => RouletteBetType::Redblack(value.try_into()?),
+        })
+    }
+}
+
+/// Bets are read through ```RouletteBet::new```, so their numbers are normalized like those of any other bet
+#[derive(Deserialize)]
+pub(crate) struct RouletteBetRepr {
+    #[serde(flatten)]
+    bet_type: RouletteBetType,
+    wager: u64,
+    #[serde(default)]
+    currency: Currency,
+    #[serde(default)]
+    announcement: Option<AnnouncedBet>,
+}
+
+impl From<RouletteBetRepr> for RouletteBet {
+    fn from(repr: RouletteBetRepr) -> Self {
+        let bet = match repr.announcement {
+            Some(announcement) => RouletteBet::announced(repr.bet_type, repr.wager, announcement),
+            None => RouletteBet::new(repr.bet_type, repr.wager),
+        };
+        bet.in_currency(repr.currency)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum AnnouncedBetRepr {
+    VoisinsDuZero,
+    Tiers,
+    Orphelins,
+    JeuZero,
+    Neighbours { number: u8, count: u8 },
+    FinaleEnPlein { digit: u8 },
+    FinaleACheval { digits: [u8; 2] },
+}
+
+impl From<AnnouncedBet> for AnnouncedBetRepr {
+    fn from(bet: AnnouncedBet) -> Self {
+        match bet {
+            AnnouncedBet::VoisinsDuZero => AnnouncedBetRepr::VoisinsDuZero,
+            AnnouncedBet::Tiers => AnnouncedBetRepr::Tiers,
+            AnnouncedBet::Orphelins => AnnouncedBetRepr::Orphelins,
+            AnnouncedBet::JeuZero => AnnouncedBetRepr::JeuZero,
+            AnnouncedBet::Neighbours { number, count } => AnnouncedBetRepr::Neighbours { number, count },
+            AnnouncedBet::FinaleEnPlein(digit) => AnnouncedBetRepr::FinaleEnPlein { digit },
+            AnnouncedBet::FinaleACheval(a, b) => AnnouncedBetRepr::FinaleACheval { digits: [a, b] },
+        }
+    }
+}
+
+impl From<AnnouncedBetRepr> for AnnouncedBet {
+    fn from(repr: AnnouncedBetRepr) -> Self {
+        match repr {
+            AnnouncedBetRepr::VoisinsDuZero => AnnouncedBet::VoisinsDuZero,
+            AnnouncedBetRepr::Tiers => AnnouncedBet::Tiers,
+            AnnouncedBetRepr::Orphelins => AnnouncedBet::Orphelins,
+            AnnouncedBetRepr::JeuZero => AnnouncedBet::JeuZero,
+            AnnouncedBetRepr::Neighbours { number, count } => AnnouncedBet::Neighbours { number, count },
+            AnnouncedBetRepr::FinaleEnPlein { digit } => AnnouncedBet::FinaleEnPlein(digit),
+            AnnouncedBetRepr::FinaleACheval { digits } => AnnouncedBet::FinaleACheval(digits[0], digits[1]),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum PlaceBetErrorRepr {
+    InvalidBetOption { bet: RouletteBet },
+    MaxBetOnOption { bet: RouletteBet, max: u64 },
+    MinBetNotSatisfied { bet: RouletteBet, min: u64 },
+    InvalidAnnouncedBet { announcement: AnnouncedBet, variant: WheelVariant },
+    TableMaxExceeded { total: u64, max: u64 },
+    PocketLiabilityExceeded { pocket: u8, liability: u64, cap: u64 },
+    BettingClosed { phase: Option<RoundPhase> },
+    RoundInProgress { round: u64 },
+    CurrencyMismatch { bet: RouletteBet, currency: Currency },
+    PayoutOverflow { bet: RouletteBet },
+}
+
+impl From<PlaceBetError> for PlaceBetErrorRepr {
+    fn from(error: PlaceBetError) -> Self {
+        match error {
+            PlaceBetError::InvalidBetOption(bet) => PlaceBetErrorRepr::InvalidBetOption { bet },
+            PlaceBetError::MaxBetOnOption(bet, max) => PlaceBetErrorRepr::MaxBetOnOption { bet, max },
+            PlaceBetError::MinBetNotSatisfied(bet, min) => PlaceBetErrorRepr::MinBetNotSatisfied { bet, min },
+            PlaceBetError::InvalidAnnouncedBet(announcement, variant) => PlaceBetErrorRepr::InvalidAnnouncedBet { announcement, variant },
+            PlaceBetError::TableMaxExceeded(total, max) => PlaceBetErrorRepr::TableMaxExceeded { total, max },
+            PlaceBetError::PocketLiabilityExceeded(pocket, liability, cap) => PlaceBetErrorRepr::PocketLiabilityExceeded { pocket, liability, cap },
+            PlaceBetError::BettingClosed(phase) => PlaceBetErrorRepr::BettingClosed { phase },
+            PlaceBetError::RoundInProgress(round) => PlaceBetErrorRepr::RoundInProgress { round },
+            PlaceBetError::CurrencyMismatch(bet, currency) => PlaceBetErrorRepr::CurrencyMismatch { bet, currency },
+            PlaceBetError::PayoutOverflow(bet) => PlaceBetErrorRepr::PayoutOverflow { bet },
+        }
+    }
+}
+
+impl From<PlaceBetErrorRepr> for PlaceBetError {
+    fn from(repr: PlaceBetErrorRepr) -> Self {
+        match repr {
+            PlaceBetErrorRepr::InvalidBetOption { bet } => PlaceBetError::InvalidBetOption(bet),
+            PlaceBetErrorRepr::MaxBetOnOption { bet, max } => PlaceBetError::MaxBetOnOption(bet, max),
+            PlaceBetErrorRepr::MinBetNotSatisfied { bet, min } => PlaceBetError::MinBetNotSatisfied(bet, min),
+            PlaceBetErrorRepr::InvalidAnnouncedBet { announcement, variant } => PlaceBetError::InvalidAnnouncedBet(announcement, variant),
+            PlaceBetErrorRepr::TableMaxExceeded { total, max } => PlaceBetError::TableMaxExceeded(total, max),
+            PlaceBetErrorRepr::PocketLiabilityExceeded { pocket, liability, cap } => PlaceBetError::PocketLiabilityExceeded(pocket, liability, cap),
+            PlaceBetErrorRepr::BettingClosed { phase } => PlaceBetError::BettingClosed(phase),
+            PlaceBetErrorRepr::RoundInProgress { round } => PlaceBetError::RoundInProgress(round),
+            PlaceBetErrorRepr::CurrencyMismatch { bet, currency } => PlaceBetError::CurrencyMismatch(bet, currency),
+            PlaceBetErrorRepr::PayoutOverflow { bet } => PlaceBetError::PayoutOverflow(bet),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use crate::outside::{Column, Dozen, Half, Parity, RedBlack};
+    use crate::rng::ScriptedSpins;
+    use crate::roulette::{Roulette, RouletteBetResult};
+    use crate::wheel::DOUBLE_ZERO;
+
+    fn round_trip<T>(value: &T, expected: serde_json::Value)
+    where
+        T: Serialize + for<'de> Deserialize<'de> + PartialEq + std::fmt::Debug,
+    {
+        let encoded = serde_json::to_value(value).unwrap();
+        assert_eq!(encoded, expected);
+        assert_eq!(&serde_json::from_value::<T>(encoded).unwrap(), value);
+    }
+
+    #[test]
+    fn bet_types() {
+        round_trip(&RouletteBetType::Straight(DOUBLE_ZERO), json!({"type": "straight", "number": 37}));
+        round_trip(&RouletteBetType::Split([10, 11]), json!({"type": "split", "numbers": [10, 11]}));
+        round_trip(&RouletteBetType::Street([1, 2, 3]), json!({"type": "street", "numbers": [1, 2, 3]}));
+        round_trip(&RouletteBetType::Basket([0, 1, 2]), json!({"type": "basket", "numbers": [0, 1, 2]}));
+        round_trip(&RouletteBetType::Topline([0, 1, 2, 3]), json!({"type": "topline", "numbers": [0, 1, 2, 3]}));
+        round_trip(&RouletteBetType::Fiveline([0, 1, 2, 3, 37]), json!({"type": "fiveline", "numbers": [0, 1, 2, 3, 37]}));
+        round_trip(&RouletteBetType::Corner([1, 2, 4, 5]), json!({"type": "corner", "numbers": [1, 2, 4, 5]}));
+        round_trip(&RouletteBetType::Doubleline([1, 2, 3, 4, 5, 6]), json!({"type": "doubleline", "numbers": [1, 2, 3, 4, 5, 6]}));
+        round_trip(&RouletteBetType::Dozens(Dozen::First), json!({"type": "dozens", "value": 1}));
+        round_trip(&RouletteBetType::Columns(Column::Second), json!({"type": "columns", "value": 2}));
+        round_trip(&RouletteBetType::EvenOdd(Parity::Even), json!({"type": "even_odd", "value": 0}));
+        round_trip(&RouletteBetType::Highlow(Half::High), json!({"type": "highlow", "value": 1}));
+        round_trip(&RouletteBetType::Redblack(RedBlack::Red), json!({"type": "redblack", "value": 0}));
+    }
+
+    #[test]
+    fn wrong_number_count_is_rejected() {
+        assert!(serde_json::from_value::<RouletteBetType>(json!({"type": "split", "numbers": [10, 11, 12]})).is_err());
+        assert!(serde_json::from_value::<RouletteBetType>(json!({"type": "square", "numbers": [1, 2, 4, 5]})).is_err());
+        assert!(serde_json::from_value::<RouletteBetType>(json!({"type": "dozens", "value": 4})).is_err());
+    }
+
+    #[test]
+    fn announced_bets() {
+        round_trip(&AnnouncedBet::VoisinsDuZero, json!({"type": "voisins_du_zero"}));
+        round_trip(&AnnouncedBet::Tiers, json!({"type": "tiers"}));
+        round_trip(&AnnouncedBet::Orphelins, json!({"type": "orphelins"}));
+        round_trip(&AnnouncedBet::JeuZero, json!({"type": "jeu_zero"}));
+        round_trip(&AnnouncedBet::Neighbours { number: 17, count: 2 }, json!({"type": "neighbours", "number": 17, "count": 2}));
+        round_trip(&AnnouncedBet::FinaleEnPlein(7), json!({"type": "finale_en_plein", "digit": 7}));
+        round_trip(&AnnouncedBet::FinaleACheval(3, 6), json!({"type": "finale_a_cheval", "digits": [3, 6]}));
+    }
+
+    #[test]
+    fn bets_and_results() {
+        let bet = RouletteBet::new(RouletteBetType::Split([16, 17]), 10);
+        round_trip(&bet, json!({"type": "split", "numbers": [16, 17], "wager": 10}));
+
+        let euros = RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 1250).in_currency(Currency::Eur);
+        round_trip(&euros, json!({"type": "redblack", "value": 0, "wager": 1250, "currency": "EUR"}));
+
+        let announced = RouletteBet::announced(RouletteBetType::Straight(17), 5, AnnouncedBet::Neighbours { number: 17, count: 2 });
+        round_trip(&announced, json!({
+            "type": "straight", "number": 17, "wager": 5,
+            "announcement": {"type": "neighbours", "number": 17, "count": 2},
+        }));
+
+        let unsorted: RouletteBet = serde_json::from_value(json!({"type": "split", "numbers": [17, 16], "wager": 10})).unwrap();
+        assert_eq!(unsorted, bet);
+        round_trip(&unsorted, json!({"type": "split", "numbers": [16, 17], "wager": 10}));
+
+        round_trip(&RouletteBetResult::new(&bet, 180), json!({
+            "bet": {"type": "split", "numbers": [16, 17], "wager": 10},
+            "win": 180,
+            "imprisoned": false,
+        }));
+    }
+
+    #[test]
+    fn settlements() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![17]));
+        let outcome = r.spin_settled(&[RouletteBet::new(RouletteBetType::Straight(17), 10)]).unwrap();
+        round_trip(&outcome, json!({
+            "spin": 1, "number": 17, "colour": "black",
+            "settlements": [{
+                "bet_id": {"spin": 1, "index": 0},
+                "bet": {"type": "straight", "number": 17, "wager": 10},
+                "payout": 360,
+                "status": "win",
+            }],
+        }));
+    }
+
+    #[test]
+    fn errors() {
+        let bet = RouletteBet::new(RouletteBetType::Straight(17), 10);
+        let bet_json = json!({"type": "straight", "number": 17, "wager": 10});
+
+        round_trip(&PlaceBetError::InvalidBetOption(bet), json!({"type": "invalid_bet_option", "bet": bet_json}));
+        round_trip(&PlaceBetError::MaxBetOnOption(bet, 5), json!({"type": "max_bet_on_option", "bet": bet_json, "max": 5}));
+        round_trip(&PlaceBetError::MinBetNotSatisfied(bet, 20), json!({"type": "min_bet_not_satisfied", "bet": bet_json, "min": 20}));
+        round_trip(&PlaceBetError::InvalidAnnouncedBet(AnnouncedBet::Tiers, WheelVariant::American), json!({
+            "type": "invalid_announced_bet", "announcement": {"type": "tiers"}, "variant": "american",
+        }));
+        round_trip(&PlaceBetError::TableMaxExceeded(1600, 1500), json!({"type": "table_max_exceeded", "total": 1600, "max": 1500}));
+        round_trip(&PlaceBetError::PocketLiabilityExceeded(17, 3600, 3000), json!({
+            "type": "pocket_liability_exceeded", "pocket": 17, "liability": 3600, "cap": 3000,
+        }));
+        round_trip(&PlaceBetError::BettingClosed(Some(RoundPhase::NoMoreBets)), json!({"type": "betting_closed", "phase": "no_more_bets"}));
+        round_trip(&PlaceBetError::BettingClosed(None), json!({"type": "betting_closed", "phase": null}));
+        round_trip(&PlaceBetError::RoundInProgress(3), json!({"type": "round_in_progress", "round": 3}));
+        round_trip(&PlaceBetError::CurrencyMismatch(bet, Currency::Gbp), json!({"type": "currency_mismatch", "bet": bet_json, "currency": "GBP"}));
+        round_trip(&PlaceBetError::PayoutOverflow(bet), json!({"type": "payout_overflow", "bet": bet_json}));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::outside::Colour;
+use crate::roulette::{RouletteBet, RouletteBetResult};
+
+/// Identifies a bet across spins: the id of the spin it was placed on (the id of its ```SpinRecord```) and its index
+/// in the bets passed to that spin, as kept in ```SpinRecord::bets```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BetId {
+    pub spin: u64,
+    pub index: usize,
+}
+
+impl BetId {
+    pub fn new(spin: u64, index: usize) -> Self {
+        Self {
+            spin,
+            index,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum SettlementStatus {
+    /// The payout is more than the stake
+    Win,
+
+    /// The stake is lost, or only part of it is returned (La Partage)
+    Lose,
+
+    /// The stake is returned and nothing more, as for a bet winning its way out of prison
+    Push,
+
+    /// The bet went to prison and is settled by the next spin
+    Imprisoned,
+}
+
+/// The settlement of a single bet. Unlike ```RouletteBetResult``` it owns its bet, so it can outlive the bets passed
+/// to the spin.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Settlement {
+    bet_id: BetId,
+    bet: RouletteBet,
+    payout: u64,
+    status: SettlementStatus,
+}
+
+impl Settlement {
+    pub(crate) fn new(bet_id: BetId, result: &RouletteBetResult) -> Self {
+        let bet = *result.bet();
+        let status = if result.is_imprisoned() {
+            SettlementStatus::Imprisoned
+        } else if result.win() > bet.wager() {
+            SettlementStatus::Win
+        } else if result.win() == bet.wager() {
+            SettlementStatus::Push
+        } else {
+            SettlementStatus::Lose
+        };
+
+        Self {
+            bet_id,
+            bet,
+            payout: result.win(),
+            status,
+        }
+    }
+
+    pub fn bet_id(&self) -> BetId {
+        self.bet_id
+    }
+
+    pub fn bet(&self) -> &RouletteBet {
+        &self.bet
+    }
+
+    pub fn stake(&self) -> u64 {
+        self.bet.wager()
+    }
+
+    /// Total returned to the player, stake included
+    pub fn payout(&self) -> u64 {
+        self.payout
+    }
+
+    /// Payout minus the stake. A bet in prison is not settled yet and nets 0; its stake is counted when it is released.
+    pub fn net(&self) -> i64 {
+        match self.status {
+            SettlementStatus::Imprisoned => 0,
+            _ => self.payout as i64 - self.stake() as i64,
+        }
+    }
+
+    pub fn status(&self) -> SettlementStatus {
+        self.status
+    }
+}
+
+/// Everything settled by a spin: the winning pocket, the settlements of the bets placed on it, in order, followed
+/// by those of the bets released from prison
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpinOutcome {
+    spin: u64,
+    number: u8,
+    colour: Colour,
+    settlements: Vec<Settlement>,
+}
+
+impl SpinOutcome {
+    pub(crate) fn new(spin: u64, number: u8, colour: Colour, settlements: Vec<Settlement>) -> Self {
+        Self {
+            spin,
+            number,
+            colour,
+            settlements,
+        }
+    }
+
+    /// The id of the spin, as recorded in the spin log
+    pub fn spin(&self) -> u64 {
+        self.spin
+    }
+
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    pub fn colour(&self) -> Colour {
+        self.colour
+    }
+
+    pub fn settlements(&self) -> &[Settlement] {
+        &self.settlements
+    }
+
+    pub fn into_settlements(self) -> Vec<Settlement> {
+        self.settlements
+    }
+
+    pub fn total_payout(&self) -> u64 {
+        self.settlements.iter().map(|s| s.payout()).sum()
+    }
+
+    pub fn net(&self) -> i64 {
+        self.settlements.iter().map(|s| s.net()).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::outside::{Parity, RedBlack};
+    use crate::rng::ScriptedSpins;
+    use crate::roulette::{Roulette, RouletteBetType, ZeroRule};
+    use crate::wheel::WheelVariant;
+
+    #[test]
+    fn settlements_outlive_the_bets() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![17]));
+        let outcome = {
+            let bets = vec![
+                RouletteBet::new(RouletteBetType::Straight(17), 10),
+                RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 20),
+            ];
+            r.spin_settled(&bets).unwrap()
+        };
+
+        assert_eq!((outcome.spin(), outcome.number(), outcome.colour()), (1, 17, Colour::Black));
+        let settlements = outcome.settlements();
+        assert_eq!(settlements[0].bet_id(), BetId::new(1, 0));
+        assert_eq!((settlements[0].stake(), settlements[0].payout(), settlements[0].net()), (10, 360, 350));
+        assert_eq!(settlements[0].status(), SettlementStatus::Win);
+        assert_eq!(settlements[1].bet_id(), BetId::new(1, 1));
+        assert_eq!((settlements[1].payout(), settlements[1].net()), (0, -20));
+        assert_eq!(settlements[1].status(), SettlementStatus::Lose);
+        assert_eq!((outcome.total_payout(), outcome.net()), (360, 330));
+    }
+
+    #[test]
+    fn bet_ids_resolve_in_the_spin_log() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![17]));
+        let bets = vec![
+            RouletteBet::new(RouletteBetType::Split([17, 16]), 10),
+            RouletteBet::new(RouletteBetType::Straight(4), 5),
+            RouletteBet::new(RouletteBetType::Split([16, 17]), 10),
+        ];
+        let outcome = r.spin_settled(&bets).unwrap();
+
+        let record = r.spin_log().get(outcome.spin()).unwrap();
+        for settlement in outcome.settlements() {
+            assert_eq!(&record.bets[settlement.bet_id().index], settlement.bet());
+        }
+        assert_eq!(record.merged_bets(), vec![
+            RouletteBet::new(RouletteBetType::Split([16, 17]), 20),
+            RouletteBet::new(RouletteBetType::Straight(4), 5),
+        ]);
+    }
+
+    #[test]
+    fn prisoners_keep_their_id() {
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![0, 10, 0]));
+        r.set_zero_rule(ZeroRule::EnPrison);
+        let bets = vec![RouletteBet::new(RouletteBetType::EvenOdd(Parity::Even), 10)];
+
+        let outcome = r.spin_settled(&bets).unwrap();
+        assert_eq!(outcome.colour(), Colour::Green);
+        assert_eq!(outcome.settlements()[0].status(), SettlementStatus::Imprisoned);
+        assert_eq!(outcome.net(), 0);
+        assert_eq!(r.imprisoned()[0].bet_id(), BetId::new(1, 0));
+
+        let outcome = r.spin_settled(&[]).unwrap();
+        assert_eq!(outcome.settlements().len(), 1);
+        assert_eq!(outcome.settlements()[0].bet_id(), BetId::new(1, 0));
+        assert_eq!(outcome.settlements()[0].status(), SettlementStatus::Push);
+        assert_eq!(outcome.net(), 0);
+
+        r.set_zero_rule(ZeroRule::LaPartage);
+        let outcome = r.spin_settled(&bets).unwrap();
+        assert_eq!(outcome.settlements()[0].bet_id(), BetId::new(3, 0));
+        assert_eq!((outcome.settlements()[0].payout(), outcome.settlements()[0].status()), (5, SettlementStatus::Lose));
+    }
+}
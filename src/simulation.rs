@@ -0,0 +1,224 @@
+use std::thread;
+use crate::rng::SeededSpins;
+use crate::roulette::{Roulette, PlaceBetError, ZeroRule};
+use crate::strategy::Strategy;
+use crate::wheel::WheelVariant;
+
+/// Monte Carlo simulation of a betting strategy: ```sessions``` independent bankrolls each play their own copy of
+/// ```strategy``` for up to ```spins``` spins, or until they can no longer cover the bets. Sessions are spread over
+/// ```threads``` threads.
+///
+/// Every session draws from its own ```StdRng```, seeded from ```master_seed``` and the session index, so a run is
+/// reproducible from the master seed whatever the number of threads.
+#[derive(Debug, Clone)]
+pub struct Simulation<S> {
+    pub variant: WheelVariant,
+    pub zero_rule: ZeroRule,
+    pub strategy: S,
+    pub starting_balance: u64,
+    pub sessions: usize,
+    pub spins: usize,
+    pub master_seed: u64,
+    pub threads: usize,
+}
+
+/// The outcome of a single simulated bankroll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionResult {
+    /// Balance before the first spin, followed by the balance after each spin played
+    pub trajectory: Vec<u64>,
+
+    /// Number of spins played before the bankroll could no longer cover the bets, if that happened
+    pub ruined_at: Option<usize>,
+
+    pub peak_balance: u64,
+    pub final_balance: u64,
+}
+
+/// All the sessions of a simulation, in session order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationReport {
+    pub sessions: Vec<SessionResult>,
+}
+
+impl<S: Strategy + Clone + Send + Sync> Simulation<S> {
+    /// A simulation of 1000 sessions of 1000 spins on a European wheel, using every available core
+    pub fn new(strategy: S, starting_balance: u64) -> Self {
+        Self {
+            variant: WheelVariant::European,
+            zero_rule: ZeroRule::Standard,
+            strategy,
+            starting_balance,
+            sessions: 1000,
+            spins: 1000,
+            master_seed: 0,
+            threads: thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        }
+    }
+
+    /// Runs every session. Fails with the errors of the first session whose strategy placed invalid bets.
+    pub fn run(&self) -> Result<SimulationReport, Vec<PlaceBetError>> {
+        let threads = self.threads.clamp(1, self.sessions.max(1));
+        let per_thread = self.sessions.div_ceil(threads);
+
+        let sessions = thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|t| {
+                    let range = (t * per_thread).min(self.sessions)..((t + 1) * per_thread).min(self.sessions);
+                    scope.spawn(move || range.map(|session| self.run_session(session)).collect::<Vec<_>>())
+                })
+                .collect();
+
+            handles.into_iter()
+                .flat_map(|handle| handle.join().expect("simulation thread panicked"))
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+
+        Ok(SimulationReport {
+            sessions,
+        })
+    }
+
+    fn table(&self, session: usize) -> Roulette<SeededSpins> {
+        let mut roulette = Roulette::from_seed(self.variant, session_seed(self.master_seed, session as u64));
+        roulette.set_zero_rule(self.zero_rule);
+        roulette
+    }
+
+    fn run_session(&self, session: usize) -> Result<SessionResult, Vec<PlaceBetError>> {
+        self.table(session).play(&mut self.strategy.clone(), self.starting_balance, self.spins)
+    }
+}
+
+/// SplitMix64 step, spreading consecutive session indices into unrelated seeds
+fn session_seed(master_seed: u64, session: u64) -> u64 {
+    let mut z = master_seed.wrapping_add(session.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl SimulationReport {
+    /// Fraction of the sessions that ran out of money
+    pub fn ruin_probability(&self) -> f64 {
+        if self.sessions.is_empty() {
+            return 0.0;
+        }
+        self.sessions.iter().filter(|s| s.ruined_at.is_some()).count() as f64 / self.sessions.len() as f64
+    }
+
+    /// Average number of spins played by the sessions that ran out of money
+    pub fn mean_time_to_ruin(&self) -> Option<f64> {
+        let times: Vec<usize> = self.sessions.iter().filter_map(|s| s.ruined_at).collect();
+        if times.is_empty() {
+            None
+        } else {
+            Some(times.iter().sum::<usize>() as f64 / times.len() as f64)
+        }
+    }
+
+    /// Highest balance reached by any session
+    pub fn peak_balance(&self) -> u64 {
+        self.sessions.iter().map(|s| s.peak_balance).max().unwrap_or(0)
+    }
+
+    /// The ```q``` quantile (0.0 to 1.0, nearest rank) of the final balances
+    pub fn final_balance_quantile(&self, q: f64) -> u64 {
+        let mut balances: Vec<u64> = self.sessions.iter().map(|s| s.final_balance).collect();
+        if balances.is_empty() {
+            return 0;
+        }
+
+        balances.sort_unstable();
+        let rank = (q.clamp(0.0, 1.0) * balances.len() as f64).ceil() as usize;
+        balances[rank.saturating_sub(1)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::outside::RedBlack;
+    use crate::roulette::{RouletteBet, RouletteBetType};
+    use crate::strategy::{FlatBet, Martingale};
+
+    fn simulation() -> Simulation<FlatBet> {
+        let mut simulation = Simulation::new(FlatBet::new(vec![RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 10)]), 100);
+        simulation.sessions = 50;
+        simulation.spins = 200;
+        simulation.master_seed = 1234;
+        simulation
+    }
+
+    #[test]
+    fn reproducible_from_master_seed() {
+        let mut single = simulation();
+        single.threads = 1;
+        let mut parallel = simulation();
+        parallel.threads = 4;
+
+        let report = single.run().unwrap();
+        assert_eq!(report, parallel.run().unwrap());
+        assert_eq!(report.sessions.len(), 50);
+
+        let mut other = simulation();
+        other.master_seed = 4321;
+        assert_ne!(report, other.run().unwrap());
+    }
+
+    #[test]
+    fn session_statistics() {
+        let report = simulation().run().unwrap();
+
+        for session in &report.sessions {
+            assert_eq!(session.trajectory[0], 100);
+            assert_eq!(*session.trajectory.last().unwrap(), session.final_balance);
+            assert_eq!(session.peak_balance, *session.trajectory.iter().max().unwrap());
+            match session.ruined_at {
+                Some(spins) => {
+                    assert_eq!(session.trajectory.len(), spins + 1);
+                    assert!(session.final_balance < 10);
+                },
+                None => assert_eq!(session.trajectory.len(), 201),
+            }
+        }
+
+        assert!(report.ruin_probability() > 0.0 && report.ruin_probability() <= 1.0);
+        assert!(report.mean_time_to_ruin().is_some());
+        assert!(report.final_balance_quantile(0.05) <= report.final_balance_quantile(0.5));
+        assert!(report.final_balance_quantile(0.5) <= report.final_balance_quantile(0.95));
+        assert_eq!(report.final_balance_quantile(1.0), report.sessions.iter().map(|s| s.final_balance).max().unwrap());
+        assert!(report.peak_balance() >= 100);
+    }
+
+    #[test]
+    fn immediate_ruin() {
+        let mut simulation = simulation();
+        simulation.starting_balance = 5;
+        let report = simulation.run().unwrap();
+
+        assert_eq!(report.ruin_probability(), 1.0);
+        assert_eq!(report.mean_time_to_ruin(), Some(0.0));
+        assert_eq!(report.final_balance_quantile(0.5), 5);
+    }
+
+    #[test]
+    fn invalid_bets() {
+        let simulation = Simulation::new(FlatBet::new(vec![RouletteBet::new(RouletteBetType::Straight(40), 10)]), 100);
+        assert!(simulation.run().is_err());
+    }
+
+    #[test]
+    fn progression() {
+        let mut simulation = Simulation::new(Martingale::new(RouletteBetType::Redblack(RedBlack::Red), 1), 100);
+        simulation.sessions = 20;
+        simulation.spins = 100;
+
+        let report = simulation.run().unwrap();
+        assert_eq!(report.sessions.len(), 20);
+        for session in &report.sessions {
+            // a martingale on 1 unit only ever finishes a cycle one unit up
+            assert!(session.peak_balance <= 100 + session.trajectory.len() as u64);
+        }
+    }
+}
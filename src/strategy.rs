@@ -0,0 +1,525 @@
+use crate::rng::SpinRng;
+use crate::outside::Half;
+use crate::roulette::{total_wager, Roulette, RouletteBet, RouletteBetType, PlaceBetError};
+use crate::simulation::SessionResult;
+
+/// What a strategy gets to see of the previous spin
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RoundOutcome {
+    pub number: u8,
+    pub wagered: u64,
+    pub returned: u64,
+}
+
+impl RoundOutcome {
+    /// A round is won when more comes back than was wagered
+    pub fn won(&self) -> bool {
+        self.returned > self.wagered
+    }
+
+    pub fn net(&self) -> i128 {
+        self.returned as i128 - self.wagered as i128
+    }
+}
+
+/// A betting strategy. Before every spin it picks the bets to place, knowing the outcome of the previous spin
+/// (```None``` before the first one) and the bankroll left. Returning no bets ends the session.
+pub trait Strategy {
+    fn next_bets(&mut self, last: Option<&RoundOutcome>, bankroll: u64) -> Vec<RouletteBet>;
+}
+
+/// Places the same bets every spin
+#[derive(Debug, Clone)]
+pub struct FlatBet {
+    bets: Vec<RouletteBet>,
+}
+
+impl FlatBet {
+    pub fn new(bets: Vec<RouletteBet>) -> Self {
+        Self {
+            bets,
+        }
+    }
+}
+
+impl Strategy for FlatBet {
+    fn next_bets(&mut self, _last: Option<&RoundOutcome>, _bankroll: u64) -> Vec<RouletteBet> {
+        self.bets.clone()
+    }
+}
+
+/// Doubles the wager after every loss and goes back to the base wager after a win, or when doubling would
+/// go over ```max_wager```.
+#[derive(Debug, Clone)]
+pub struct Martingale {
+    bet_type: RouletteBetType,
+    base: u64,
+    max_wager: u64,
+    wager: u64,
+}
+
+impl Martingale {
+    pub fn new(bet_type: RouletteBetType, base: u64) -> Self {
+        Self {
+            bet_type,
+            base,
+            max_wager: u64::MAX,
+            wager: base,
+        }
+    }
+
+    pub fn set_max_wager(&mut self, max_wager: u64) {
+        self.max_wager = max_wager;
+    }
+}
+
+impl Strategy for Martingale {
+    fn next_bets(&mut self, last: Option<&RoundOutcome>, _bankroll: u64) -> Vec<RouletteBet> {
+        self.wager = match last {
+            Some(outcome) if !outcome.won() => match self.wager.checked_mul(2) {
+                Some(wager) if wager <= self.max_wager => wager,
+                _ => self.base,
+            },
+            _ => self.base,
+        };
+
+        vec![RouletteBet::new(self.bet_type, self.wager)]
+    }
+}
+
+/// Reverse Martingale: doubles the wager after every win, up to ```streak``` wins in a row, and goes back to
+/// the base wager after a loss, a completed streak, or when doubling would overflow.
+#[derive(Debug, Clone)]
+pub struct Paroli {
+    bet_type: RouletteBetType,
+    base: u64,
+    streak: u32,
+    wins: u32,
+    wager: u64,
+}
+
+impl Paroli {
+    /// The classic Paroli stops after 3 wins in a row
+    pub fn new(bet_type: RouletteBetType, base: u64) -> Self {
+        Self::with_streak(bet_type, base, 3)
+    }
+
+    pub fn with_streak(bet_type: RouletteBetType, base: u64, streak: u32) -> Self {
+        Self {
+            bet_type,
+            base,
+            streak,
+            wins: 0,
+            wager: base,
+        }
+    }
+}
+
+impl Strategy for Paroli {
+    fn next_bets(&mut self, last: Option<&RoundOutcome>, _bankroll: u64) -> Vec<RouletteBet> {
+        match last {
+            Some(outcome) if outcome.won() && self.wins + 1 < self.streak && self.wager.checked_mul(2).is_some() => {
+                self.wins += 1;
+                self.wager *= 2;
+            },
+            _ => {
+                self.wins = 0;
+                self.wager = self.base;
+            },
+        }
+
+        vec![RouletteBet::new(self.bet_type, self.wager)]
+    }
+}
+
+/// Adds a unit to the wager after a loss and takes one off after a win, never going below one unit. Goes back to
+/// one unit when adding one would overflow.
+#[derive(Debug, Clone)]
+pub struct DAlembert {
+    bet_type: RouletteBetType,
+    unit: u64,
+    wager: u64,
+}
+
+impl DAlembert {
+    pub fn new(bet_type: RouletteBetType, unit: u64) -> Self {
+        Self {
+            bet_type,
+            unit,
+            wager: unit,
+        }
+    }
+}
+
+impl Strategy for DAlembert {
+    fn next_bets(&mut self, last: Option<&RoundOutcome>, _bankroll: u64) -> Vec<RouletteBet> {
+        match last {
+            Some(outcome) if outcome.won() => self.wager = self.wager.saturating_sub(self.unit).max(self.unit),
+            Some(_) => self.wager = self.wager.checked_add(self.unit).unwrap_or(self.unit),
+            None => (),
+        }
+
+        vec![RouletteBet::new(self.bet_type, self.wager)]
+    }
+}
+
+/// Wagers follow the Fibonacci sequence (1, 1, 2, 3, 5, ...) in units: one step forward after a loss,
+/// two steps back after a win. The sequence starts over when the wager would overflow.
+#[derive(Debug, Clone)]
+pub struct Fibonacci {
+    bet_type: RouletteBetType,
+    unit: u64,
+    step: usize,
+}
+
+impl Fibonacci {
+    pub fn new(bet_type: RouletteBetType, unit: u64) -> Self {
+        Self {
+            bet_type,
+            unit,
+            step: 0,
+        }
+    }
+
+    /// The number of units at ```step```, or ```None``` if it overflows
+    fn units(step: usize) -> Option<u64> {
+        let (mut a, mut b) = (1u64, 1u64);
+        for _ in 0..step {
+            let next = a.checked_add(b)?;
+            a = b;
+            b = next;
+        }
+        Some(a)
+    }
+}
+
+impl Strategy for Fibonacci {
+    fn next_bets(&mut self, last: Option<&RoundOutcome>, _bankroll: u64) -> Vec<RouletteBet> {
+        match last {
+            Some(outcome) if outcome.won() => self.step = self.step.saturating_sub(2),
+            Some(_) => self.step += 1,
+            None => (),
+        }
+
+        let wager = match Self::units(self.step).and_then(|units| units.checked_mul(self.unit)) {
+            Some(wager) => wager,
+            None => {
+                self.step = 0;
+                self.unit
+            },
+        };
+        vec![RouletteBet::new(self.bet_type, wager)]
+    }
+}
+
+/// Cancellation system: the wager is the sum of the first and last numbers of the line (in units). A win crosses
+/// both off, a loss adds the lost amount to the end of the line. Once the line is empty it starts over, as it does
+/// when the wager would overflow (betting a single unit if even the initial line overflows).
+#[derive(Debug, Clone)]
+pub struct Labouchere {
+    bet_type: RouletteBetType,
+    unit: u64,
+    initial: Vec<u64>,
+    line: Vec<u64>,
+    units: u64,
+}
+
+impl Labouchere {
+    pub fn new(bet_type: RouletteBetType, unit: u64, line: Vec<u64>) -> Self {
+        assert!(!line.is_empty(), "Labouchere needs a line to start from");
+        Self {
+            bet_type,
+            unit,
+            initial: line.clone(),
+            line,
+            units: 0,
+        }
+    }
+
+    /// The numbers left on the line
+    pub fn line(&self) -> &[u64] {
+        self.line.as_slice()
+    }
+
+    /// The units and the wager for the current line, or ```None``` if they overflow
+    fn line_wager(&self) -> Option<(u64, u64)> {
+        let units = match self.line.len() {
+            1 => self.line[0],
+            n => self.line[0].checked_add(self.line[n - 1])?,
+        };
+        Some((units, units.checked_mul(self.unit)?))
+    }
+}
+
+impl Strategy for Labouchere {
+    fn next_bets(&mut self, last: Option<&RoundOutcome>, _bankroll: u64) -> Vec<RouletteBet> {
+        match last {
+            Some(outcome) if outcome.won() => {
+                self.line.pop();
+                if !self.line.is_empty() {
+                    self.line.remove(0);
+                }
+            },
+            Some(_) => self.line.push(self.units),
+            None => (),
+        }
+
+        if self.line.is_empty() {
+            self.line = self.initial.clone();
+        }
+
+        let (units, wager) = match self.line_wager() {
+            Some(next) => next,
+            None => {
+                self.line = self.initial.clone();
+                self.line_wager().unwrap_or((1, self.unit))
+            },
+        };
+        self.units = units;
+        vec![RouletteBet::new(self.bet_type, wager)]
+    }
+}
+
+/// Oscar's Grind aims for a profit of one unit per cycle. The wager goes up a unit after each win (but never
+/// more than needed to finish the cycle one unit up) and stays put after a loss. The cycle starts over when the
+/// wager would overflow.
+#[derive(Debug, Clone)]
+pub struct OscarsGrind {
+    bet_type: RouletteBetType,
+    unit: u64,
+    wager: u64,
+    profit: i128,
+}
+
+impl OscarsGrind {
+    pub fn new(bet_type: RouletteBetType, unit: u64) -> Self {
+        Self {
+            bet_type,
+            unit,
+            wager: unit,
+            profit: 0,
+        }
+    }
+}
+
+impl Strategy for OscarsGrind {
+    fn next_bets(&mut self, last: Option<&RoundOutcome>, _bankroll: u64) -> Vec<RouletteBet> {
+        if let Some(outcome) = last {
+            self.profit += outcome.net();
+            let target = self.unit as i128;
+
+            if self.profit >= target {
+                // cycle complete
+                self.profit = 0;
+                self.wager = self.unit;
+            } else if outcome.won() {
+                match self.wager.checked_add(self.unit) {
+                    Some(wager) => self.wager = (wager as i128).min(target - self.profit) as u64,
+                    None => {
+                        self.profit = 0;
+                        self.wager = self.unit;
+                    },
+                }
+            }
+        }
+
+        vec![RouletteBet::new(self.bet_type, self.wager)]
+    }
+}
+
+/// James Bond coverage: per 20 units, 14 on 19-36, 5 on the 13-18 double line and 1 on zero
+#[derive(Debug, Clone)]
+pub struct JamesBond {
+    unit: u64,
+}
+
+impl JamesBond {
+    pub fn new(unit: u64) -> Self {
+        Self {
+            unit,
+        }
+    }
+}
+
+impl Strategy for JamesBond {
+    fn next_bets(&mut self, _last: Option<&RoundOutcome>, _bankroll: u64) -> Vec<RouletteBet> {
+        vec![
+            RouletteBet::new(RouletteBetType::Highlow(Half::High), 14 * self.unit),
+            RouletteBet::new(RouletteBetType::Doubleline([13, 14, 15, 16, 17, 18]), 5 * self.unit),
+            RouletteBet::new(RouletteBetType::Straight(0), self.unit),
+        ]
+    }
+}
+
+impl<R: SpinRng> Roulette<R> {
+    /// Plays ```strategy``` from ```bankroll``` for up to ```max_spins``` spins. The session stops early when the
+    /// strategy has no more bets, or when the bankroll cannot cover them (recorded as ruin). Fails with
+    /// ```PayoutOverflow``` for the winning bets if their payout would take the bankroll past ```u64::MAX```.
+    pub fn play<S: Strategy>(&mut self, strategy: &mut S, bankroll: u64, max_spins: usize) -> Result<SessionResult, Vec<PlaceBetError>> {
+        let mut balance = bankroll;
+        let mut trajectory = vec![balance];
+        let mut ruined_at = None;
+        let mut last = None;
+
+        for spin in 0..max_spins {
+            let bets = strategy.next_bets(last.as_ref(), balance);
+            if bets.is_empty() {
+                break;
+            }
+
+            let wagered = total_wager(&bets, self.limits().currency()).map_err(|error| vec![error])?.minor_units();
+            if wagered > balance {
+                ruined_at = Some(spin);
+                break;
+            }
+
+            balance -= wagered;
+            let (number, results) = self.spin(&bets)?;
+            let returned = match results.iter()
+                .try_fold(0u64, |total, result| total.checked_add(result.win()))
+                .filter(|returned| balance.checked_add(*returned).is_some())
+            {
+                Some(returned) => returned,
+                None => return Err(results.iter()
+                    .filter(|result| result.win() > 0)
+                    .map(|result| PlaceBetError::PayoutOverflow(*result.bet()))
+                    .collect()),
+            };
+            balance += returned;
+            trajectory.push(balance);
+
+            last = Some(RoundOutcome {
+                number,
+                wagered,
+                returned,
+            });
+        }
+
+        Ok(SessionResult {
+            peak_balance: trajectory.iter().copied().max().unwrap_or(balance),
+            final_balance: balance,
+            trajectory,
+            ruined_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::outside::RedBlack;
+    use crate::rng::ScriptedSpins;
+    use crate::wheel::WheelVariant;
+
+    const RED: RouletteBetType = RouletteBetType::Redblack(RedBlack::Red);
+
+    fn win(wager: u64) -> RoundOutcome {
+        RoundOutcome { number: 1, wagered: wager, returned: wager.saturating_mul(2) }
+    }
+
+    fn loss(wager: u64) -> RoundOutcome {
+        RoundOutcome { number: 2, wagered: wager, returned: 0 }
+    }
+
+    /// Feeds a sequence of wins (true) and losses (false) to a single bet strategy, returning the wagers it placed
+    fn wagers<S: Strategy>(strategy: &mut S, outcomes: &[bool]) -> Vec<u64> {
+        let mut wager = strategy.next_bets(None, 1000)[0].wager();
+        let mut wagers = vec![wager];
+        for &won in outcomes {
+            let outcome = if won { win(wager) } else { loss(wager) };
+            wager = strategy.next_bets(Some(&outcome), 1000)[0].wager();
+            wagers.push(wager);
+        }
+        wagers
+    }
+
+    #[test]
+    fn martingale() {
+        let mut martingale = Martingale::new(RED, 10);
+        martingale.set_max_wager(40);
+        assert_eq!(wagers(&mut martingale, &[false, false, true, false, false, false]), vec![10, 20, 40, 10, 20, 40, 10]);
+    }
+
+    #[test]
+    fn paroli() {
+        let mut paroli = Paroli::new(RED, 10);
+        assert_eq!(wagers(&mut paroli, &[true, true, true, true, false]), vec![10, 20, 40, 10, 20, 10]);
+
+        let base = u64::MAX / 4 + 1;
+        let mut paroli = Paroli::with_streak(RED, base, 5);
+        assert_eq!(wagers(&mut paroli, &[true, true, true]), vec![base, base * 2, base, base * 2]);
+    }
+
+    #[test]
+    fn dalembert() {
+        let mut dalembert = DAlembert::new(RED, 5);
+        assert_eq!(wagers(&mut dalembert, &[false, false, true, true, true]), vec![5, 10, 15, 10, 5, 5]);
+
+        let unit = u64::MAX / 3;
+        let mut dalembert = DAlembert::new(RED, unit);
+        assert_eq!(wagers(&mut dalembert, &[false, false, false, false]), vec![unit, unit * 2, unit * 3, unit, unit * 2]);
+    }
+
+    #[test]
+    fn fibonacci() {
+        let mut fibonacci = Fibonacci::new(RED, 10);
+        assert_eq!(wagers(&mut fibonacci, &[false, false, false, false, true, true]), vec![10, 10, 20, 30, 50, 20, 10]);
+
+        let unit = u64::MAX / 4;
+        let mut fibonacci = Fibonacci::new(RED, unit);
+        assert_eq!(wagers(&mut fibonacci, &[false, false, false, false, false]), vec![unit, unit, unit * 2, unit * 3, unit, unit]);
+    }
+
+    #[test]
+    fn labouchere() {
+        let mut labouchere = Labouchere::new(RED, 10, vec![1, 2, 3]);
+        assert_eq!(wagers(&mut labouchere, &[false, true, true]), vec![40, 50, 50, 40]);
+        // 1 2 3 -> lose 4 -> 1 2 3 4 -> win 5 -> 2 3 -> win 5 -> empty, starts over
+        assert_eq!(labouchere.line(), &[1, 2, 3]);
+
+        // 1 2 3 -> lose 4 -> 1 2 3 4, where 5 units overflow: starts over
+        let unit = u64::MAX / 4;
+        let mut labouchere = Labouchere::new(RED, unit, vec![1, 2, 3]);
+        assert_eq!(wagers(&mut labouchere, &[false]), vec![unit * 4, unit * 4]);
+        assert_eq!(labouchere.line(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn oscars_grind() {
+        let mut oscar = OscarsGrind::new(RED, 10);
+        // -10, -20, win 10 -> -10, win 20 capped at 20 -> +10, cycle done
+        assert_eq!(wagers(&mut oscar, &[false, false, true, true]), vec![10, 10, 10, 20, 10]);
+
+        // -u, -2u, win u -> -u, going up a unit would overflow: starts over
+        let unit = u64::MAX / 2 + 1;
+        let mut oscar = OscarsGrind::new(RED, unit);
+        assert_eq!(wagers(&mut oscar, &[false, false, true, false]), vec![unit, unit, unit, unit, unit]);
+    }
+
+    #[test]
+    fn james_bond() {
+        let mut bond = JamesBond::new(10);
+        let bets = bond.next_bets(None, 1000);
+        assert_eq!(bets.iter().map(|b| b.wager()).sum::<u64>(), 200);
+    }
+
+    #[test]
+    fn play_on_roulette() {
+        // black, black, red: the martingale wins back its losses plus the base wager
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![2, 4, 1]));
+        let session = r.play(&mut Martingale::new(RED, 10), 100, 3).unwrap();
+        assert_eq!(session.trajectory, vec![100, 90, 70, 110]);
+        assert_eq!(session.ruined_at, None);
+
+        // can't cover the fourth doubling
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![2]));
+        let session = r.play(&mut Martingale::new(RED, 10), 100, 10).unwrap();
+        assert_eq!(session.trajectory, vec![100, 90, 70, 30]);
+        assert_eq!(session.ruined_at, Some(3));
+
+        // the bankroll can't hold the payout
+        let mut r = Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(vec![1]));
+        let bet = RouletteBet::new(RED, 10);
+        assert_eq!(r.play(&mut FlatBet::new(vec![bet]), u64::MAX - 5, 1).err(), Some(vec![PlaceBetError::PayoutOverflow(bet)]));
+    }
+}
@@ -0,0 +1,494 @@
+use std::collections::HashMap;
+use std::fmt;
+use rand::rngs::ThreadRng;
+use crate::limits::TableLimits;
+use crate::money::Currency;
+use crate::outside::Colour;
+use crate::rng::SpinRng;
+use crate::roulette::{merge_bets, total_wager, Roulette, RouletteBet, PlaceBetError};
+use crate::settlement::{BetId, Settlement, SettlementStatus};
+use crate::wallet::{Ledger, Wallet, WalletError};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerId(pub u64);
+
+impl fmt::Display for PlayerId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "player {}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableError {
+    TableFull,
+    NoSuchSeat(usize),
+    SeatTaken(usize),
+    AlreadySeated(PlayerId),
+    NotSeated(PlayerId),
+    Bets(Vec<PlaceBetError>),
+    Wallet(WalletError),
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TableError::TableFull => write!(f, "All the seats are taken"),
+            TableError::NoSuchSeat(seat) => write!(f, "There is no seat {}", seat),
+            TableError::SeatTaken(seat) => write!(f, "Seat {} is taken", seat),
+            TableError::AlreadySeated(player) => write!(f, "{} is already seated", player),
+            TableError::NotSeated(player) => write!(f, "{} is not seated at the table", player),
+            TableError::Bets(errors) => {
+                let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "{}", errors.join(", "))
+            },
+            TableError::Wallet(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+/// What a spin settled for one player. Bets released from prison are settled to the player who placed them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerStatement {
+    player: PlayerId,
+    wagered: u64,
+    settlements: Vec<Settlement>,
+}
+
+impl PlayerStatement {
+    fn new(player: PlayerId) -> Self {
+        Self {
+            player,
+            wagered: 0,
+            settlements: Vec::new(),
+        }
+    }
+
+    pub fn player(&self) -> PlayerId {
+        self.player
+    }
+
+    /// Total staked on this spin. Bets released from prison were staked on an earlier one.
+    pub fn wagered(&self) -> u64 {
+        self.wagered
+    }
+
+    pub fn settlements(&self) -> &[Settlement] {
+        &self.settlements
+    }
+
+    /// Total returned to the player, stakes included
+    pub fn payout(&self) -> u64 {
+        self.settlements.iter().map(|s| s.payout()).sum()
+    }
+
+    pub fn net(&self) -> i64 {
+        self.settlements.iter().map(|s| s.net()).sum()
+    }
+}
+
+/// The result of a spin at a table: the winning pocket and a statement per player, in seat order. Players who left
+/// with bets in prison get a statement when those bets are released, after the seated players.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableOutcome {
+    spin: u64,
+    number: u8,
+    colour: Colour,
+    statements: Vec<PlayerStatement>,
+}
+
+impl TableOutcome {
+    /// The id of the spin, as recorded in the spin log
+    pub fn spin(&self) -> u64 {
+        self.spin
+    }
+
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    pub fn colour(&self) -> Colour {
+        self.colour
+    }
+
+    pub fn statements(&self) -> &[PlayerStatement] {
+        &self.statements
+    }
+
+    pub fn statement(&self, player: PlayerId) -> Option<&PlayerStatement> {
+        self.statements.iter().find(|s| s.player == player)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Seat {
+    player: PlayerId,
+    bets: Vec<RouletteBet>,
+}
+
+/// A table with seats, gathering the bets of each player for the next spin of its ```Roulette```.
+/// The limits and liability cap of the roulette apply to the bets of all the players together, the player limits to
+/// the bets of each player. Stakes are reserved in the ```Wallet``` when bets are placed and settled by the spin.
+#[derive(Debug, Clone)]
+pub struct Table<R = ThreadRng, W = Ledger> {
+    roulette: Roulette<R>,
+    wallet: W,
+    seats: Vec<Option<Seat>>,
+    player_limits: TableLimits,
+
+    /// Who placed the bets waiting in prison
+    prisoners: HashMap<BetId, PlayerId>,
+}
+
+impl<R: SpinRng> Table<R> {
+    /// Creates a table keeping the players' funds in an empty ```Ledger```
+    pub fn new(roulette: Roulette<R>, seats: usize) -> Self {
+        Self::with_wallet(roulette, seats, Ledger::new())
+    }
+}
+
+impl<R: SpinRng, W: Wallet> Table<R, W> {
+    pub fn with_wallet(roulette: Roulette<R>, seats: usize, wallet: W) -> Self {
+        Self {
+            roulette,
+            wallet,
+            seats: vec![None; seats],
+            player_limits: TableLimits::default(),
+            prisoners: HashMap::new(),
+        }
+    }
+
+    pub fn roulette(&self) -> &Roulette<R> {
+        &self.roulette
+    }
+
+    pub fn roulette_mut(&mut self) -> &mut Roulette<R> {
+        &mut self.roulette
+    }
+
+    pub fn wallet(&self) -> &W {
+        &self.wallet
+    }
+
+    /// The wallet, to credit or debit the players. Reserved funds should be left to the table.
+    pub fn wallet_mut(&mut self) -> &mut W {
+        &mut self.wallet
+    }
+
+    pub fn player_limits(&self) -> &TableLimits {
+        &self.player_limits
+    }
+
+    /// Sets the limits applied to the bets of each player
+    pub fn set_player_limits(&mut self, limits: TableLimits) {
+        self.player_limits = limits;
+    }
+
+    pub fn seat_count(&self) -> usize {
+        self.seats.len()
+    }
+
+    /// The player in each seat
+    pub fn players(&self) -> Vec<Option<PlayerId>> {
+        self.seats.iter().map(|seat| seat.as_ref().map(|s| s.player)).collect()
+    }
+
+    pub fn seat_of(&self, player: PlayerId) -> Option<usize> {
+        self.seats.iter().position(|seat| matches!(seat, Some(s) if s.player == player))
+    }
+
+    /// Seats the player in the first free seat
+    pub fn sit(&mut self, player: PlayerId) -> Result<usize, TableError> {
+        let seat = self.seats.iter().position(|seat| seat.is_none()).ok_or(TableError::TableFull)?;
+        self.sit_at(player, seat)?;
+        Ok(seat)
+    }
+
+    pub fn sit_at(&mut self, player: PlayerId, seat: usize) -> Result<(), TableError> {
+        if self.seat_of(player).is_some() {
+            return Err(TableError::AlreadySeated(player));
+        }
+        match self.seats.get_mut(seat) {
+            None => Err(TableError::NoSuchSeat(seat)),
+            Some(Some(_)) => Err(TableError::SeatTaken(seat)),
+            Some(free) => {
+                *free = Some(Seat {
+                    player,
+                    bets: Vec::new(),
+                });
+                Ok(())
+            },
+        }
+    }
+
+    /// Frees the player's seat, releasing the stakes of the bets they had placed for the next spin, which are
+    /// returned. Their bets in prison stay there and are still settled to them.
+    pub fn leave(&mut self, player: PlayerId) -> Result<Vec<RouletteBet>, TableError> {
+        let seat = self.seat_of(player).ok_or(TableError::NotSeated(player))?;
+        let stake = staked(self.bets_of(player), self.roulette.limits().currency())?;
+        let bets = self.seats[seat].take().map(|s| s.bets).unwrap_or_default();
+        self.wallet.release(player, stake).map_err(TableError::Wallet)?;
+        Ok(bets)
+    }
+
+    /// The bets placed by the player for the next spin
+    pub fn bets_of(&self, player: PlayerId) -> &[RouletteBet] {
+        match self.seat_of(player).and_then(|seat| self.seats[seat].as_ref()) {
+            Some(seat) => &seat.bets,
+            None => &[],
+        }
+    }
+
+    /// Adds bets for the player. They are checked together with the player's other bets against the player limits,
+    /// and together with everyone's bets against the table. Their stakes are then reserved, all of them or none.
+    pub fn place_bets(&mut self, player: PlayerId, bets: &[RouletteBet]) -> Result<(), TableError> {
+        let seat = self.seat_of(player).ok_or(TableError::NotSeated(player))?;
+        let mine: Vec<RouletteBet> = self.bets_of(player).iter().chain(bets).copied().collect();
+
+        let mut errors: Vec<PlaceBetError> = merge_bets(&mine)
+            .iter()
+            .filter_map(|bet| self.player_limits.check_bet(bet))
+            .collect();
+        if let Some(error) = self.player_limits.check_total(&mine) {
+            errors.push(error)
+        }
+        if !errors.is_empty() {
+            return Err(TableError::Bets(errors));
+        }
+
+        let all: Vec<RouletteBet> = self.all_bets().into_iter().map(|(_, bet)| bet).chain(bets.iter().copied()).collect();
+        self.roulette.validate_bets(&all).map_err(TableError::Bets)?;
+        self.wallet.reserve(player, staked(bets, self.roulette.limits().currency())?).map_err(TableError::Wallet)?;
+
+        if let Some(seat) = &mut self.seats[seat] {
+            seat.bets = mine;
+        }
+        Ok(())
+    }
+
+    /// Spins with the bets of all the players and settles each of them separately, posting the stakes and payouts
+    /// to the wallet. If the bets no longer pass validation (the table limits changed, or rounds are running) they are
+    /// all returned: their reservations are released and the seats cleared.
+    pub fn spin(&mut self) -> Result<TableOutcome, TableError> {
+        let (owners, bets): (Vec<PlayerId>, Vec<RouletteBet>) = self.all_bets().into_iter().unzip();
+        let outcome = match self.roulette.spin_settled(&bets) {
+            Ok(outcome) => outcome,
+            Err(errors) => {
+                let currency = self.roulette.limits().currency();
+                for seat in self.seats.iter_mut().flatten() {
+                    let bets = std::mem::take(&mut seat.bets);
+                    self.wallet.release(seat.player, staked(&bets, currency)?).map_err(TableError::Wallet)?;
+                }
+                return Err(TableError::Bets(errors));
+            },
+        };
+
+        let mut statements: Vec<PlayerStatement> = self.seats
+            .iter_mut()
+            .flatten()
+            .map(|seat| {
+                seat.bets.clear();
+                PlayerStatement::new(seat.player)
+            })
+            .collect();
+
+        for settlement in outcome.settlements() {
+            let bet_id = settlement.bet_id();
+            let player = if bet_id.spin == outcome.spin() {
+                owners[bet_id.index]
+            } else {
+                match self.prisoners.remove(&bet_id) {
+                    Some(player) => player,
+                    None => continue,
+                }
+            };
+            if settlement.status() == SettlementStatus::Imprisoned {
+                self.prisoners.insert(bet_id, player);
+            } else {
+                self.wallet.settle(player, settlement.stake(), settlement.payout()).map_err(TableError::Wallet)?;
+            }
+
+            let statement = match statements.iter().position(|s| s.player == player) {
+                Some(i) => &mut statements[i],
+                None => {
+                    statements.push(PlayerStatement::new(player));
+                    statements.last_mut().unwrap()
+                },
+            };
+            if bet_id.spin == outcome.spin() {
+                statement.wagered += settlement.stake();
+            }
+            statement.settlements.push(*settlement);
+        }
+
+        Ok(TableOutcome {
+            spin: outcome.spin(),
+            number: outcome.number(),
+            colour: outcome.colour(),
+            statements,
+        })
+    }
+
+    /// The bets of all the players in seat order
+    fn all_bets(&self) -> Vec<(PlayerId, RouletteBet)> {
+        self.seats
+            .iter()
+            .flatten()
+            .flat_map(|seat| seat.bets.iter().map(move |bet| (seat.player, *bet)))
+            .collect()
+    }
+}
+
+fn staked(bets: &[RouletteBet], currency: Currency) -> Result<u64, TableError> {
+    total_wager(bets, currency)
+        .map(|total| total.minor_units())
+        .map_err(|error| TableError::Bets(vec![error]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::limits::BetLimit;
+    use crate::outside::{Parity, RedBlack};
+    use crate::rng::ScriptedSpins;
+    use crate::roulette::{RouletteBetType, ZeroRule};
+    use crate::wheel::WheelVariant;
+
+    const ALICE: PlayerId = PlayerId(1);
+    const BOB: PlayerId = PlayerId(2);
+
+    fn table(spins: Vec<u8>) -> Table<ScriptedSpins> {
+        let mut t = Table::new(Roulette::with_rng(WheelVariant::European, ScriptedSpins::new(spins)), 2);
+        t.wallet_mut().credit(ALICE, 1000).unwrap();
+        t.wallet_mut().credit(BOB, 1000).unwrap();
+        t
+    }
+
+    #[test]
+    fn seats() {
+        let mut t = table(vec![0]);
+        assert_eq!(t.sit(ALICE), Ok(0));
+        assert_eq!(t.sit(ALICE), Err(TableError::AlreadySeated(ALICE)));
+        assert_eq!(t.sit_at(BOB, 0), Err(TableError::SeatTaken(0)));
+        assert_eq!(t.sit_at(BOB, 2), Err(TableError::NoSuchSeat(2)));
+        assert_eq!(t.sit(BOB), Ok(1));
+        assert_eq!(t.sit(PlayerId(3)), Err(TableError::TableFull));
+        assert_eq!(t.players(), vec![Some(ALICE), Some(BOB)]);
+
+        let bet = RouletteBet::new(RouletteBetType::Straight(7), 5);
+        t.place_bets(ALICE, &[bet]).unwrap();
+        assert_eq!(t.leave(ALICE), Ok(vec![bet]));
+        assert_eq!(t.place_bets(ALICE, &[bet]), Err(TableError::NotSeated(ALICE)));
+        assert_eq!(t.sit(PlayerId(3)), Ok(0));
+    }
+
+    #[test]
+    fn players_are_settled_separately() {
+        let mut t = table(vec![17]);
+        t.sit(ALICE).unwrap();
+        t.sit(BOB).unwrap();
+        t.place_bets(ALICE, &[RouletteBet::new(RouletteBetType::Straight(17), 10)]).unwrap();
+        t.place_bets(BOB, &[RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 20)]).unwrap();
+        t.place_bets(ALICE, &[RouletteBet::new(RouletteBetType::Straight(18), 10)]).unwrap();
+
+        let outcome = t.spin().unwrap();
+        assert_eq!((outcome.number(), outcome.colour()), (17, Colour::Black));
+
+        let alice = outcome.statement(ALICE).unwrap();
+        assert_eq!(alice.settlements().len(), 2);
+        assert_eq!((alice.wagered(), alice.payout(), alice.net()), (20, 360, 340));
+
+        let bob = outcome.statement(BOB).unwrap();
+        assert_eq!((bob.wagered(), bob.payout(), bob.net()), (20, 0, -20));
+        assert!(t.bets_of(ALICE).is_empty());
+
+        assert_eq!((t.wallet().available(ALICE), t.wallet().reserved(ALICE)), (1340, 0));
+        assert_eq!((t.wallet().available(BOB), t.wallet().reserved(BOB)), (980, 0));
+    }
+
+    #[test]
+    fn stakes_are_reserved() {
+        let mut t = table(vec![0]);
+        t.sit(ALICE).unwrap();
+        t.sit(BOB).unwrap();
+
+        let bets = [RouletteBet::new(RouletteBetType::Straight(7), 600), RouletteBet::new(RouletteBetType::Straight(8), 300)];
+        t.place_bets(ALICE, &bets).unwrap();
+        assert_eq!((t.wallet().available(ALICE), t.wallet().reserved(ALICE)), (100, 900));
+
+        // all or nothing
+        assert_eq!(t.place_bets(ALICE, &bets[1..]), Err(TableError::Wallet(WalletError::InsufficientFunds(ALICE, 100, 300))));
+        assert_eq!(t.bets_of(ALICE), &bets);
+
+        assert_eq!(t.leave(ALICE), Ok(bets.to_vec()));
+        assert_eq!((t.wallet().available(ALICE), t.wallet().reserved(ALICE)), (1000, 0));
+
+        // the limits are lowered before the spin: the round is called off and the stakes released
+        t.place_bets(BOB, &bets).unwrap();
+        let mut limits = TableLimits::default();
+        limits.set_table_max(500);
+        t.roulette_mut().set_limits(limits);
+        assert_eq!(t.spin(), Err(TableError::Bets(vec![PlaceBetError::TableMaxExceeded(900, 500)])));
+        assert!(t.bets_of(BOB).is_empty());
+        assert_eq!((t.wallet().available(BOB), t.wallet().reserved(BOB)), (1000, 0));
+        assert!(t.roulette().history().is_empty());
+    }
+
+    #[test]
+    fn player_and_table_limits() {
+        let mut t = table(vec![0]);
+        t.sit(ALICE).unwrap();
+        t.sit(BOB).unwrap();
+
+        let mut player_limits = TableLimits::new(BetLimit::new(1, 50), BetLimit::new(5, 200));
+        player_limits.set_table_max(150);
+        t.set_player_limits(player_limits);
+        let mut table_limits = TableLimits::default();
+        table_limits.set_table_max(250);
+        t.roulette_mut().set_limits(table_limits);
+
+        // the per spot limit counts the player's earlier bets
+        let straight = RouletteBet::new(RouletteBetType::Straight(7), 30);
+        t.place_bets(ALICE, &[straight]).unwrap();
+        assert_eq!(t.place_bets(ALICE, &[straight]), Err(TableError::Bets(vec![
+            PlaceBetError::MaxBetOnOption(RouletteBet::new(RouletteBetType::Straight(7), 60), 50),
+        ])));
+
+        let red = RouletteBet::new(RouletteBetType::Redblack(RedBlack::Red), 130);
+        assert_eq!(t.place_bets(ALICE, &[red]), Err(TableError::Bets(vec![PlaceBetError::TableMaxExceeded(160, 150)])));
+
+        // each player is within their limits, together they are over the table max
+        t.place_bets(BOB, &[RouletteBet::new(RouletteBetType::Redblack(RedBlack::Black), 150)]).unwrap();
+        assert_eq!(t.place_bets(ALICE, &[RouletteBet::new(RouletteBetType::EvenOdd(Parity::Odd), 100)]), Err(TableError::Bets(vec![
+            PlaceBetError::TableMaxExceeded(280, 250),
+        ])));
+        assert_eq!(t.bets_of(ALICE), &[straight]);
+    }
+
+    #[test]
+    fn prisoners_are_settled_to_their_owner() {
+        let mut t = table(vec![0, 2]);
+        t.roulette_mut().set_zero_rule(ZeroRule::EnPrison);
+        t.sit(ALICE).unwrap();
+        t.sit(BOB).unwrap();
+        t.place_bets(ALICE, &[RouletteBet::new(RouletteBetType::EvenOdd(Parity::Even), 10)]).unwrap();
+
+        let outcome = t.spin().unwrap();
+        assert_eq!(outcome.statement(ALICE).unwrap().settlements()[0].status(), SettlementStatus::Imprisoned);
+        assert_eq!((t.wallet().available(ALICE), t.wallet().reserved(ALICE)), (990, 10));
+
+        // released after Alice left
+        t.leave(ALICE).unwrap();
+        let outcome = t.spin().unwrap();
+        assert_eq!(outcome.statements().len(), 2);
+        assert_eq!(outcome.statements()[0].player(), BOB);
+        let alice = outcome.statement(ALICE).unwrap();
+        assert_eq!((alice.wagered(), alice.payout(), alice.net()), (0, 10, 0));
+        assert_eq!(alice.settlements()[0].status(), SettlementStatus::Push);
+        assert_eq!((t.wallet().available(ALICE), t.wallet().reserved(ALICE)), (1000, 0));
+    }
+}
@@ -0,0 +1,258 @@
+//! Player funds.
+//!
+//! A ```Wallet``` holds the money of the players. Stakes are reserved when bets are accepted, so they can't be spent
+//! twice, then either settled (the stake goes to the house and the payout comes back from it) or released back to the
+//! player. ```Ledger``` is an in-memory implementation keeping a double-entry journal of every movement.
+
+use std::collections::HashMap;
+use std::fmt;
+use crate::table::PlayerId;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WalletError {
+    /// The player's available funds (second field) don't cover the amount (third field)
+    InsufficientFunds(PlayerId, u64, u64),
+
+    /// The player's reserved funds (second field) don't cover the amount (third field)
+    InsufficientReserved(PlayerId, u64, u64),
+
+    /// Adding the amount (second field) would take the player's funds past ```u64::MAX```
+    BalanceOverflow(PlayerId, u64),
+}
+
+impl fmt::Display for WalletError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WalletError::InsufficientFunds(player, available, amount) => write!(f, "{} has {} available, {} needed", player, available, amount),
+            WalletError::InsufficientReserved(player, reserved, amount) => write!(f, "{} has {} reserved, {} needed", player, reserved, amount),
+            WalletError::BalanceOverflow(player, amount) => write!(f, "{} can't hold {} more", player, amount),
+        }
+    }
+}
+
+impl std::error::Error for WalletError {}
+
+pub trait Wallet {
+    /// Funds the player can bet or withdraw
+    fn available(&self, player: PlayerId) -> u64;
+
+    /// Funds held for bets that are not settled yet
+    fn reserved(&self, player: PlayerId) -> u64;
+
+    /// Adds funds to the player's available balance. Fails if the player's funds would no longer fit in a ```u64```.
+    fn credit(&mut self, player: PlayerId, amount: u64) -> Result<(), WalletError>;
+
+    /// Takes funds out of the player's available balance
+    fn debit(&mut self, player: PlayerId, amount: u64) -> Result<(), WalletError>;
+
+    /// Holds the amount for bets. Either all of it is reserved or nothing is.
+    fn reserve(&mut self, player: PlayerId, amount: u64) -> Result<(), WalletError>;
+
+    /// Gives reserved funds back to the available balance, for bets that were not played
+    fn release(&mut self, player: PlayerId, amount: u64) -> Result<(), WalletError>;
+
+    /// Settles a bet: the reserved stake goes to the house and the payout (stake included, if any) comes back
+    /// from it to the available balance. Fails, settling nothing, if the payout would overflow the player's funds.
+    fn settle(&mut self, player: PlayerId, stake: u64, payout: u64) -> Result<(), WalletError>;
+}
+
+/// An account of the ledger. Money comes into the ledger from ```Cash``` and leaves through it, so its balance is
+/// negative by what the players hold.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Account {
+    Cash,
+    House,
+    Available(PlayerId),
+    Reserved(PlayerId),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    Deposit,
+    Withdrawal,
+    Reservation,
+    Release,
+    Stake,
+    Payout,
+}
+
+/// A movement of funds between two accounts
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+    pub id: u64,
+    pub kind: EntryKind,
+    pub from: Account,
+    pub to: Account,
+    pub amount: u64,
+}
+
+/// An in-memory double-entry ledger. Every operation is posted as entries moving funds from one account to another,
+/// so the balances of all the accounts always add up to zero.
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    entries: Vec<LedgerEntry>,
+    balances: HashMap<Account, i128>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All the entries, in the order they were posted
+    pub fn entries(&self) -> &[LedgerEntry] {
+        &self.entries
+    }
+
+    pub fn balance(&self, account: Account) -> i128 {
+        self.balances.get(&account).copied().unwrap_or(0)
+    }
+
+    fn post(&mut self, kind: EntryKind, from: Account, to: Account, amount: u64) {
+        if amount == 0 {
+            return;
+        }
+        *self.balances.entry(from).or_insert(0) -= amount as i128;
+        *self.balances.entry(to).or_insert(0) += amount as i128;
+        self.entries.push(LedgerEntry {
+            id: self.entries.len() as u64 + 1,
+            kind,
+            from,
+            to,
+            amount,
+        });
+    }
+
+    fn check_available(&self, player: PlayerId, amount: u64) -> Result<(), WalletError> {
+        let available = self.available(player);
+        if available < amount {
+            return Err(WalletError::InsufficientFunds(player, available, amount));
+        }
+        Ok(())
+    }
+
+    /// Checks that the player's funds, available and reserved together, can take ```amount``` once ```leaving```
+    /// has been taken out, so neither balance can overflow a ```u64```
+    fn check_headroom(&self, player: PlayerId, leaving: u64, amount: u64) -> Result<(), WalletError> {
+        let held = self.balance(Account::Available(player)) + self.balance(Account::Reserved(player)) - leaving as i128;
+        if held + amount as i128 > u64::MAX as i128 {
+            return Err(WalletError::BalanceOverflow(player, amount));
+        }
+        Ok(())
+    }
+
+    fn check_reserved(&self, player: PlayerId, amount: u64) -> Result<(), WalletError> {
+        let reserved = self.reserved(player);
+        if reserved < amount {
+            return Err(WalletError::InsufficientReserved(player, reserved, amount));
+        }
+        Ok(())
+    }
+}
+
+impl Wallet for Ledger {
+    fn available(&self, player: PlayerId) -> u64 {
+        self.balance(Account::Available(player)) as u64
+    }
+
+    fn reserved(&self, player: PlayerId) -> u64 {
+        self.balance(Account::Reserved(player)) as u64
+    }
+
+    fn credit(&mut self, player: PlayerId, amount: u64) -> Result<(), WalletError> {
+        self.check_headroom(player, 0, amount)?;
+        self.post(EntryKind::Deposit, Account::Cash, Account::Available(player), amount);
+        Ok(())
+    }
+
+    fn debit(&mut self, player: PlayerId, amount: u64) -> Result<(), WalletError> {
+        self.check_available(player, amount)?;
+        self.post(EntryKind::Withdrawal, Account::Available(player), Account::Cash, amount);
+        Ok(())
+    }
+
+    fn reserve(&mut self, player: PlayerId, amount: u64) -> Result<(), WalletError> {
+        self.check_available(player, amount)?;
+        self.post(EntryKind::Reservation, Account::Available(player), Account::Reserved(player), amount);
+        Ok(())
+    }
+
+    fn release(&mut self, player: PlayerId, amount: u64) -> Result<(), WalletError> {
+        self.check_reserved(player, amount)?;
+        self.post(EntryKind::Release, Account::Reserved(player), Account::Available(player), amount);
+        Ok(())
+    }
+
+    fn settle(&mut self, player: PlayerId, stake: u64, payout: u64) -> Result<(), WalletError> {
+        self.check_reserved(player, stake)?;
+        self.check_headroom(player, stake, payout)?;
+        self.post(EntryKind::Stake, Account::Reserved(player), Account::House, stake);
+        self.post(EntryKind::Payout, Account::House, Account::Available(player), payout);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PLAYER: PlayerId = PlayerId(1);
+
+    #[test]
+    fn reservations() {
+        let mut ledger = Ledger::new();
+        ledger.credit(PLAYER, 100).unwrap();
+
+        assert_eq!(ledger.reserve(PLAYER, 150), Err(WalletError::InsufficientFunds(PLAYER, 100, 150)));
+        assert_eq!(ledger.available(PLAYER), 100);
+
+        ledger.reserve(PLAYER, 60).unwrap();
+        assert_eq!((ledger.available(PLAYER), ledger.reserved(PLAYER)), (40, 60));
+        assert_eq!(ledger.debit(PLAYER, 50), Err(WalletError::InsufficientFunds(PLAYER, 40, 50)));
+
+        ledger.release(PLAYER, 20).unwrap();
+        assert_eq!(ledger.release(PLAYER, 50), Err(WalletError::InsufficientReserved(PLAYER, 40, 50)));
+
+        // a lost bet and a winning one
+        ledger.settle(PLAYER, 10, 0).unwrap();
+        ledger.settle(PLAYER, 30, 60).unwrap();
+        assert_eq!((ledger.available(PLAYER), ledger.reserved(PLAYER)), (120, 0));
+        assert_eq!(ledger.balance(Account::House), -20);
+
+        ledger.debit(PLAYER, 120).unwrap();
+        assert_eq!(ledger.available(PLAYER), 0);
+    }
+
+    #[test]
+    fn balances_fit_in_a_u64() {
+        let mut ledger = Ledger::new();
+        ledger.credit(PLAYER, u64::MAX - 10).unwrap();
+        assert_eq!(ledger.credit(PLAYER, 11), Err(WalletError::BalanceOverflow(PLAYER, 11)));
+
+        // reserved funds count too
+        ledger.reserve(PLAYER, 100).unwrap();
+        assert_eq!(ledger.credit(PLAYER, 11), Err(WalletError::BalanceOverflow(PLAYER, 11)));
+        assert_eq!(ledger.settle(PLAYER, 100, 111), Err(WalletError::BalanceOverflow(PLAYER, 111)));
+        assert_eq!(ledger.reserved(PLAYER), 100);
+
+        ledger.settle(PLAYER, 100, 110).unwrap();
+        assert_eq!(ledger.available(PLAYER), u64::MAX);
+    }
+
+    #[test]
+    fn entries_balance_out() {
+        let mut ledger = Ledger::new();
+        ledger.credit(PLAYER, 100).unwrap();
+        ledger.credit(PlayerId(2), 50).unwrap();
+        ledger.reserve(PLAYER, 100).unwrap();
+        ledger.settle(PLAYER, 100, 0).unwrap();
+
+        let kinds: Vec<EntryKind> = ledger.entries().iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec![EntryKind::Deposit, EntryKind::Deposit, EntryKind::Reservation, EntryKind::Stake]);
+
+        let accounts = [Account::Cash, Account::House, Account::Available(PLAYER), Account::Reserved(PLAYER), Account::Available(PlayerId(2))];
+        assert_eq!(accounts.iter().map(|a| ledger.balance(*a)).sum::<i128>(), 0);
+        assert_eq!(ledger.balance(Account::Cash), -150);
+        assert_eq!(ledger.balance(Account::House), 100);
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+
+/// The pocket number used to represent "00" on an American wheel. It sorts after 36, so bet arrays containing it
+/// keep their ascending order.
+pub const DOUBLE_ZERO: u8 = 37;
+
+/// Pockets in the order they appear around the wheel, clockwise starting from zero
+const EUROPEAN_WHEEL_ORDER: [u8; 37] = [
+    0, 32, 15, 19, 4, 21, 2, 25, 17, 34, 6, 27, 13, 36, 11, 30, 8, 23, 10,
+    5, 24, 16, 33, 1, 20, 14, 31, 9, 22, 18, 29, 7, 28, 12, 35, 3, 26,
+];
+const AMERICAN_WHEEL_ORDER: [u8; 38] = [
+    0, 28, 9, 26, 30, 11, 7, 20, 32, 17, 5, 22, 34, 15, 3, 24, 36, 13, 1,
+    DOUBLE_ZERO, 27, 10, 25, 29, 12, 8, 19, 31, 18, 6, 21, 33, 16, 4, 23, 35, 14, 2,
+];
+
+/// The wheel (and matching table layout) a `Roulette` is played on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum WheelVariant {
+    /// Single zero wheel, 37 pockets (0-36)
+    #[default]
+    European,
+
+    /// Double zero wheel, 38 pockets (0-36 and 00)
+    American,
+}
+
+impl fmt::Display for WheelVariant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WheelVariant::European => write!(f, "European"),
+            WheelVariant::American => write!(f, "American"),
+        }
+    }
+}
+
+impl WheelVariant {
+    /// Number of pockets on the wheel. Pockets are numbered from 0 up to (but excluding) this value.
+    pub fn pocket_count(&self) -> usize {
+        match self {
+            WheelVariant::European => 37,
+            WheelVariant::American => 38,
+        }
+    }
+
+    /// All the pockets on the wheel in numeric order
+    pub fn pockets(&self) -> impl Iterator<Item = u8> {
+        0..self.pocket_count() as u8
+    }
+
+    /// The green pockets that lose every outside bet
+    pub fn zeros(&self) -> &'static [u8] {
+        match self {
+            WheelVariant::European => &[0],
+            WheelVariant::American => &[0, DOUBLE_ZERO],
+        }
+    }
+
+    pub fn has_pocket(&self, number: u8) -> bool {
+        (number as usize) < self.pocket_count()
+    }
+
+    pub fn is_zero(&self, number: u8) -> bool {
+        self.zeros().contains(&number)
+    }
+
+    /// The pockets in the order they appear around the wheel, clockwise starting from zero
+    pub fn wheel_order(&self) -> &'static [u8] {
+        match self {
+            WheelVariant::European => &EUROPEAN_WHEEL_ORDER,
+            WheelVariant::American => &AMERICAN_WHEEL_ORDER,
+        }
+    }
+
+    /// Index of ```number``` in ```wheel_order()```
+    pub fn wheel_position(&self, number: u8) -> Option<usize> {
+        self.wheel_order().iter().position(|&n| n == number)
+    }
+
+    /// ```number``` together with ```count``` pockets either side of it, in wheel order.
+    /// Returns ```None``` if the number is not on the wheel, or if the neighbours would wrap around onto each other.
+    pub fn neighbours(&self, number: u8, count: u8) -> Option<Vec<u8>> {
+        let order = self.wheel_order();
+        let position = self.wheel_position(number)?;
+        let count = count as usize;
+        if count * 2 + 1 > order.len() {
+            return None;
+        }
+
+        Some((0..=count * 2)
+            .map(|offset| order[(position + order.len() - count + offset) % order.len()])
+            .collect())
+    }
+}
+
+/// Displays a pocket number the way it is printed on the table, i.e. ```DOUBLE_ZERO``` shows as "00".
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Pocket(pub u8);
+
+impl fmt::Display for Pocket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0 == DOUBLE_ZERO {
+            write!(f, "00")
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pockets_per_variant() {
+        assert_eq!(WheelVariant::European.pockets().count(), 37);
+        assert_eq!(WheelVariant::American.pockets().count(), 38);
+        assert!(!WheelVariant::European.has_pocket(DOUBLE_ZERO));
+        assert!(WheelVariant::American.has_pocket(DOUBLE_ZERO));
+        assert!(!WheelVariant::American.has_pocket(38));
+    }
+
+    #[test]
+    fn wheel_order_has_every_pocket() {
+        for &variant in &[WheelVariant::European, WheelVariant::American] {
+            let mut order = variant.wheel_order().to_vec();
+            order.sort();
+            assert_eq!(order, variant.pockets().collect::<Vec<u8>>());
+        }
+    }
+
+    #[test]
+    fn neighbours() {
+        assert_eq!(WheelVariant::European.neighbours(17, 2), Some(vec![2, 25, 17, 34, 6]));
+        assert_eq!(WheelVariant::European.neighbours(0, 1), Some(vec![26, 0, 32]));
+        assert_eq!(WheelVariant::European.neighbours(26, 0), Some(vec![26]));
+        assert_eq!(WheelVariant::American.neighbours(1, 1), Some(vec![13, 1, DOUBLE_ZERO]));
+        assert_eq!(WheelVariant::European.neighbours(17, 18).map(|n| n.len()), Some(37));
+        assert_eq!(WheelVariant::European.neighbours(17, 19), None);
+        assert_eq!(WheelVariant::European.neighbours(DOUBLE_ZERO, 1), None);
+    }
+
+    #[test]
+    fn pocket_display() {
+        assert_eq!(Pocket(0).to_string(), "0");
+        assert_eq!(Pocket(36).to_string(), "36");
+        assert_eq!(Pocket(DOUBLE_ZERO).to_string(), "00");
+    }
+}